@@ -1,12 +1,38 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer, MintTo, SetAuthority},
+    token::{self, Mint, Token, TokenAccount, Transfer, MintTo, Burn, SetAuthority},
+};
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
 };
 use spl_token::instruction::AuthorityType;
 
 declare_id!("GreenChain11111111111111111111111111111111");
 
+// Limits used for validation and account sizing
+pub const MAX_NAME_LEN: usize = 100;
+pub const MAX_DESCRIPTION_LEN: usize = 500;
+pub const MAX_LOCATION_LEN: usize = 100;
+pub const MAX_BENEFICIARY_LEN: usize = 100;
+pub const MAX_RETIREMENT_REASON_LEN: usize = 200;
+pub const MAX_METHODOLOGY_LEN: usize = 64;
+pub const SATELLITE_HASH_LEN: usize = 64;
+pub const MAX_MILESTONES: usize = 10;
+pub const MAX_ORACLES: usize = 10;
+pub const CREDITS_PRECISION: u128 = 1_000_000_000_000; // Fixed-point scale for credits_per_share
+pub const DEFAULT_BUFFER_BPS: u16 = 1000; // Share of each issuance held back against reversals
+pub const BONUS_POOL_BPS: u16 = 2000; // Share of verified CO2 reserved for tier bonuses
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+pub const MAX_FEE_RECIPIENTS: usize = 5;
+pub const CONFIG_TIMELOCK: i64 = 2 * 24 * 60 * 60; // Delay before queued config changes apply
+pub const MAX_ATTESTATION_AGE: i64 = 3600; // Seconds an attestation stays relayable
+pub const ATTESTATION_DOMAIN: &[u8] = b"greenchain:progress-attestation:v1";
+pub const COMMUNITY_REPORT_BOND: u64 = 50_000_000; // 0.05 SOL posted with each community report
+pub const COMMUNITY_REPORT_BOUNTY: u64 = 100_000_000; // 0.1 SOL paid for a confirmed report
+
 #[program]
 pub mod greenchain_reforestation {
     use super::*;
@@ -39,6 +65,12 @@ pub mod greenchain_reforestation {
         platform_state.is_initialized = true;
         platform_state.last_ai_update = Clock::get()?.unix_timestamp;
         platform_state.platform_version = 2; // Enhanced version
+        platform_state.pending_authority = Pubkey::default();
+        platform_state.pending_config = None;
+        platform_state.total_credits_retired = 0;
+        platform_state.total_retirements = 0;
+        platform_state.next_credit_serial = 1;
+        platform_state.buffer_bps = DEFAULT_BUFFER_BPS;
         platform_state.bump = *ctx.bumps.get("platform_state").unwrap();
         
         emit!(PlatformInitialized {
@@ -60,22 +92,41 @@ pub mod greenchain_reforestation {
         location: String,
         target_trees: u64,
         target_funding: u64,
-        ai_health_score: u8,
-        ai_confidence: u8,
-        ai_risk_assessment: u8,
         satellite_verification_hash: String,
         expected_co2_per_tree: u32,
         project_tier: ProjectTier,
+        milestones: Vec<Milestone>,
+        funding_deadline: i64,
+        min_funding_bps: u16,
+        methodology: String,
     ) -> Result<()> {
         require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
-        require!(name.len() <= 100, ErrorCode::NameTooLong);
-        require!(description.len() <= 500, ErrorCode::DescriptionTooLong);
+        require!(name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+        require!(description.len() <= MAX_DESCRIPTION_LEN, ErrorCode::DescriptionTooLong);
+        require!(location.len() <= MAX_LOCATION_LEN, ErrorCode::LocationTooLong);
         require!(target_trees > 0, ErrorCode::InvalidTargetTrees);
         require!(target_funding > 0, ErrorCode::InvalidTargetFunding);
-        require!(ai_confidence >= ctx.accounts.platform_state.min_ai_confidence, ErrorCode::InsufficientAIConfidence);
-        require!(ai_health_score >= 60, ErrorCode::InsufficientHealthScore);
-        require!(ai_risk_assessment <= 40, ErrorCode::ExcessiveRisk);
-        require!(satellite_verification_hash.len() == 64, ErrorCode::InvalidSatelliteHash);
+        require!(satellite_verification_hash.len() == SATELLITE_HASH_LEN, ErrorCode::InvalidSatelliteHash);
+        require!(methodology.len() <= MAX_METHODOLOGY_LEN, ErrorCode::MethodologyTooLong);
+
+        // Milestones must be ordered by tree count and release exactly 100% of escrowed funds
+        require!(!milestones.is_empty() && milestones.len() <= MAX_MILESTONES, ErrorCode::InvalidMilestones);
+        let mut previous_trees = 0u64;
+        let mut total_release_bps = 0u32;
+        for milestone in milestones.iter() {
+            require!(milestone.trees_target > previous_trees, ErrorCode::InvalidMilestones);
+            require!(milestone.trees_target <= target_trees, ErrorCode::InvalidMilestones);
+            require!(milestone.release_bps > 0, ErrorCode::InvalidMilestones);
+            previous_trees = milestone.trees_target;
+            total_release_bps += milestone.release_bps as u32;
+        }
+        require!(total_release_bps == 10000, ErrorCode::InvalidMilestones);
+        // The final milestone sweeps the escrow, so it must coincide with the project's target
+        require!(previous_trees == target_trees, ErrorCode::InvalidMilestones);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(funding_deadline > current_time, ErrorCode::InvalidFundingDeadline);
+        require!(min_funding_bps > 0 && min_funding_bps <= 10000, ErrorCode::InvalidMinFunding);
 
         let project = &mut ctx.accounts.project;
         project.id = project_id;
@@ -87,17 +138,45 @@ pub mod greenchain_reforestation {
         project.target_funding = target_funding;
         project.current_funding = 0;
         project.trees_planted = 0;
-        project.status = ProjectStatus::Active;
-        project.created_at = Clock::get()?.unix_timestamp;
-        project.ai_health_score = ai_health_score;
-        project.ai_confidence = ai_confidence;
-        project.ai_risk_assessment = ai_risk_assessment;
+        project.status = ProjectStatus::PendingReview; // Not investable until a reviewer approves
+        project.created_at = current_time;
+        project.ai_health_score = 0; // Set by the oracle in assess_project
+        project.ai_confidence = 0;
+        project.ai_risk_assessment = 0;
+        project.assessed_at = 0;
         project.satellite_verification_hash = satellite_verification_hash;
         project.expected_co2_per_tree = expected_co2_per_tree;
         project.project_tier = project_tier;
         project.last_ai_update = Clock::get()?.unix_timestamp;
         project.carbon_credits_minted = 0;
+        project.co2_sequestered = 0;
+        project.bonus_credits_issued = 0;
+        project.co2_accrued = 0;
+        project.credits_per_share = 0;
+        project.bonus_weight = 0;
+        project.bonus_per_weight = 0;
+        project.methodology = methodology;
+        project.credit_batch_count = 0;
+        project.credit_mint = Pubkey::default(); // Platform mint until create_project_mint
+        project.buffer_credits = 0;
+        project.buffer_cancelled = 0;
+        project.anomaly_count = 0;
+        project.suspending_anomalies = 0;
+        project.status_before_suspension = None;
+        project.community_report_count = 0;
+        project.reversal_count = 0;
+        project.reversal_investor_loss = 0;
+        project.reversal_debited = 0;
+        project.reversal_investors_applied = 0;
+        project.total_reversed = 0;
         project.total_investors = 0;
+        project.funding_mint = ctx.accounts.funding_mint.key();
+        project.milestones = milestones;
+        project.milestones_released = 0;
+        project.funds_released = 0;
+        project.funding_deadline = funding_deadline;
+        project.min_funding_bps = min_funding_bps;
+        project.escrow_bump = *ctx.bumps.get("project_escrow").unwrap();
         project.bump = *ctx.bumps.get("project").unwrap();
 
         // Update platform state
@@ -109,8 +188,6 @@ pub mod greenchain_reforestation {
             creator: ctx.accounts.creator.key(),
             target_trees,
             target_funding,
-            ai_health_score,
-            ai_confidence,
             project_tier,
             satellite_hash: satellite_verification_hash,
         });
@@ -118,29 +195,121 @@ pub mod greenchain_reforestation {
         Ok(())
     }
 
+    /// Record the AI oracle's initial assessment of a project under review
+    pub fn assess_project(
+        ctx: Context<AssessProject>,
+        ai_health_score: u8,
+        ai_confidence: u8,
+        ai_risk_assessment: u8,
+    ) -> Result<()> {
+        require!(ctx.accounts.ai_oracle.key() == ctx.accounts.platform_state.ai_oracle_authority, ErrorCode::UnauthorizedAIOracle);
+        require!(ai_health_score <= 100 && ai_confidence <= 100 && ai_risk_assessment <= 100, ErrorCode::InvalidAssessment);
+
+        let project = &mut ctx.accounts.project;
+        require!(project.status == ProjectStatus::PendingReview, ErrorCode::ProjectNotPendingReview);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        project.ai_health_score = ai_health_score;
+        project.ai_confidence = ai_confidence;
+        project.ai_risk_assessment = ai_risk_assessment;
+        project.assessed_at = current_time;
+        project.last_ai_update = current_time;
+
+        emit!(ProjectAssessed {
+            project_id: project.id,
+            ai_oracle: ctx.accounts.ai_oracle.key(),
+            ai_health_score,
+            ai_confidence,
+            ai_risk_assessment,
+        });
+
+        Ok(())
+    }
+
+    /// Give a project its own carbon credit mint so its credits trade separately from the platform token;
+    /// only possible before its first issuance, so all of its batches share one mint
+    pub fn create_project_mint(ctx: Context<CreateProjectMint>) -> Result<()> {
+        require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
+        require!(ctx.accounts.creator.key() == ctx.accounts.project.creator, ErrorCode::UnauthorizedProjectCreator);
+
+        let project = &mut ctx.accounts.project;
+        require!(project.credit_mint == Pubkey::default(), ErrorCode::ProjectMintExists);
+        require!(project.credit_batch_count == 0, ErrorCode::CreditsAlreadyIssued);
+        require!(
+            !matches!(project.status, ProjectStatus::Failed | ProjectStatus::Rejected),
+            ErrorCode::ProjectNotActive
+        );
+        project.credit_mint = ctx.accounts.project_mint.key();
+
+        emit!(ProjectMintCreated {
+            project_id: project.id,
+            mint: project.credit_mint,
+        });
+
+        Ok(())
+    }
+
+    /// Approve an assessed project for fundraising if the oracle's scores meet platform thresholds
+    pub fn approve_project(ctx: Context<ApproveProject>) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(project.status == ProjectStatus::PendingReview, ErrorCode::ProjectNotPendingReview);
+        require!(project.assessed_at != 0, ErrorCode::ProjectNotAssessed);
+        require!(project.ai_confidence >= ctx.accounts.platform_state.min_ai_confidence, ErrorCode::InsufficientAIConfidence);
+        require!(project.ai_health_score >= 60, ErrorCode::InsufficientHealthScore);
+        require!(project.ai_risk_assessment <= 40, ErrorCode::ExcessiveRisk);
+        require!(Clock::get()?.unix_timestamp <= project.funding_deadline, ErrorCode::FundingDeadlinePassed);
+
+        project.status = ProjectStatus::Active;
+
+        emit!(ProjectApproved {
+            project_id: project.id,
+            reviewer: ctx.accounts.reviewer.key(),
+            ai_health_score: project.ai_health_score,
+            ai_confidence: project.ai_confidence,
+            ai_risk_assessment: project.ai_risk_assessment,
+        });
+
+        Ok(())
+    }
+
+    /// Reject a project under review with a reason code
+    pub fn reject_project(ctx: Context<RejectProject>, reason_code: u16) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(project.status == ProjectStatus::PendingReview, ErrorCode::ProjectNotPendingReview);
+
+        project.status = ProjectStatus::Rejected;
+        project.rejection_reason = reason_code;
+
+        emit!(ProjectRejected {
+            project_id: project.id,
+            reviewer: ctx.accounts.reviewer.key(),
+            reason_code,
+        });
+
+        Ok(())
+    }
+
     /// Enhanced investment function with multi-tier system and AI-based bonuses
     pub fn invest_in_project(
         ctx: Context<InvestInProject>,
         amount: u64,
-        investment_tier: InvestmentTier,
     ) -> Result<()> {
         require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
         require!(amount > 0, ErrorCode::InvalidInvestmentAmount);
         require!(ctx.accounts.project.status == ProjectStatus::Active, ErrorCode::ProjectNotActive);
+        require!(ctx.accounts.project.assessed_at != 0, ErrorCode::ProjectNotAssessed);
         
         let project = &mut ctx.accounts.project;
+        require!(Clock::get()?.unix_timestamp <= project.funding_deadline, ErrorCode::FundingDeadlinePassed);
         require!(project.current_funding + amount <= project.target_funding, ErrorCode::ExceedsTargetFunding);
 
-        // Calculate tier-based bonuses
-        let (min_amount, bonus_multiplier) = match investment_tier {
-            InvestmentTier::Bronze => (1_000_000, 100), // 1 SOL, 1x
-            InvestmentTier::Silver => (5_000_000, 110), // 5 SOL, 1.1x
-            InvestmentTier::Gold => (10_000_000, 125), // 10 SOL, 1.25x
-            InvestmentTier::Platinum => (50_000_000, 150), // 50 SOL, 1.5x
-            InvestmentTier::Diamond => (100_000_000, 200), // 100 SOL, 2x
-        };
-
-        require!(amount >= min_amount, ErrorCode::InsufficientTierAmount);
+        // Tier follows the investor's cumulative position, so top-ups can move it up
+        let previous_amount = ctx.accounts.investment.amount;
+        let total_amount = previous_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let (investment_tier, bonus_multiplier) = tier_for_amount(total_amount)
+            .ok_or(ErrorCode::InsufficientTierAmount)?;
 
         // AI-based bonus calculation
         let ai_bonus = if project.ai_health_score >= 90 && project.ai_confidence >= 95 {
@@ -156,7 +325,7 @@ pub mod greenchain_reforestation {
         // Transfer investment amount
         let cpi_accounts = Transfer {
             from: ctx.accounts.investor_token_account.to_account_info(),
-            to: ctx.accounts.project_treasury.to_account_info(),
+            to: ctx.accounts.project_escrow.to_account_info(),
             authority: ctx.accounts.investor.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -168,29 +337,37 @@ pub mod greenchain_reforestation {
         if platform_fee > 0 {
             let fee_accounts = Transfer {
                 from: ctx.accounts.investor_token_account.to_account_info(),
-                to: ctx.accounts.platform_treasury.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
                 authority: ctx.accounts.investor.to_account_info(),
             };
             let fee_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), fee_accounts);
             token::transfer(fee_ctx, platform_fee)?;
         }
 
-        // Create or update investment record
+        // Create the investment record on first deposit, accumulate on top-ups
         let investment = &mut ctx.accounts.investment;
-        investment.project_id = project.id;
-        investment.investor = ctx.accounts.investor.key();
-        investment.amount = amount;
+        let is_new_investor = investment.investor == Pubkey::default();
+        if is_new_investor {
+            investment.project_id = project.id;
+            investment.investor = ctx.accounts.investor.key();
+            investment.is_active = true;
+            investment.is_refunded = false;
+            investment.carbon_credits_earned = 0;
+            investment.reversals_applied = project.reversal_count; // Earlier reversals predate this position
+            investment.bump = *ctx.bumps.get("investment").unwrap();
+        }
+
+        record_deposit(project, investment, amount, total_multiplier)?;
         investment.investment_tier = investment_tier;
-        investment.bonus_multiplier = total_multiplier;
         investment.timestamp = Clock::get()?.unix_timestamp;
-        investment.is_active = true;
-        investment.carbon_credits_earned = 0;
-        investment.bump = *ctx.bumps.get("investment").unwrap();
+        investment.deposit_count += 1;
 
         // Update project and platform state
-        project.current_funding += amount;
-        project.total_investors += 1;
-        
+        if is_new_investor {
+            project.total_investors += 1;
+            project.reversal_investors_applied += 1;
+        }
+
         let platform_state = &mut ctx.accounts.platform_state;
         platform_state.total_investments += amount;
 
@@ -203,15 +380,17 @@ pub mod greenchain_reforestation {
             project_id: project.id,
             investor: ctx.accounts.investor.key(),
             amount,
+            total_amount,
+            deposit_index: investment.deposit_count,
             investment_tier,
-            bonus_multiplier: total_multiplier,
+            bonus_multiplier: investment.bonus_multiplier,
             ai_bonus,
         });
 
         Ok(())
     }
 
-    /// Update project with AI-verified progress and satellite data
+    /// Submit an oracle progress report; the median report is applied once a quorum agrees
     pub fn update_project_progress(
         ctx: Context<UpdateProjectProgress>,
         trees_planted: u64,
@@ -220,76 +399,413 @@ pub mod greenchain_reforestation {
         satellite_verification_hash: String,
         co2_sequestered: u64,
     ) -> Result<()> {
+        let oracle = ctx.accounts.ai_oracle.key();
+        let round_bump = *ctx.bumps.get("oracle_round").unwrap();
+        submit_progress_report(
+            &mut ctx.accounts.project,
+            &ctx.accounts.platform_state,
+            &ctx.accounts.oracle_set,
+            &mut ctx.accounts.oracle_round,
+            &mut ctx.accounts.oracle_stake,
+            oracle,
+            round_bump,
+            ProgressReport {
+                trees_planted,
+                ai_health_score,
+                ai_confidence,
+                co2_sequestered,
+                satellite_verification_hash,
+            },
+        )
+    }
+
+    /// Relay an oracle's Ed25519-signed progress attestation, so the oracle needs no transaction-signing hot key
+    pub fn submit_progress_attestation(
+        ctx: Context<SubmitProgressAttestation>,
+        oracle: Pubkey,
+        attestation: ProgressAttestation,
+    ) -> Result<()> {
+        // The preceding instruction must be the Ed25519 precompile verifying this exact payload
+        let message = attestation_message(ctx.program_id, &ctx.accounts.platform_state, &attestation)?;
+        verify_ed25519_instruction(&ctx.accounts.instructions_sysvar, &oracle, &message)?;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            attestation.timestamp <= current_time && current_time - attestation.timestamp <= MAX_ATTESTATION_AGE,
+            ErrorCode::StaleAttestation
+        );
+
+        require!(attestation.project_id == ctx.accounts.project.id, ErrorCode::ProjectMismatch);
+
+        // Nonces only need to increase per oracle, so quorum members can attest independently
+        let attestation_nonce = &mut ctx.accounts.attestation_nonce;
+        require!(attestation.nonce > attestation_nonce.nonce, ErrorCode::AttestationReplayed);
+        attestation_nonce.nonce = attestation.nonce;
+        attestation_nonce.bump = *ctx.bumps.get("attestation_nonce").unwrap();
+
+        emit!(AttestationSubmitted {
+            project_id: attestation.project_id,
+            oracle,
+            nonce: attestation.nonce,
+            relayer: ctx.accounts.relayer.key(),
+        });
+
+        let round_bump = *ctx.bumps.get("oracle_round").unwrap();
+        submit_progress_report(
+            &mut ctx.accounts.project,
+            &ctx.accounts.platform_state,
+            &ctx.accounts.oracle_set,
+            &mut ctx.accounts.oracle_round,
+            &mut ctx.accounts.oracle_stake,
+            oracle,
+            round_bump,
+            ProgressReport {
+                trees_planted: attestation.trees_planted,
+                ai_health_score: attestation.ai_health_score,
+                ai_confidence: attestation.ai_confidence,
+                co2_sequestered: attestation.co2_sequestered,
+                satellite_verification_hash: attestation.satellite_verification_hash,
+            },
+        )
+    }
+
+    /// Set how long agreed progress reports stay open to challenge and the bond a challenger must post
+    pub fn configure_dispute_window(
+        ctx: Context<UpdateOracleSet>,
+        challenge_period: i64,
+        dispute_bond: u64,
+    ) -> Result<()> {
+        require!(challenge_period >= 0, ErrorCode::InvalidOracleSet);
+
+        let oracle_set = &mut ctx.accounts.oracle_set;
+        oracle_set.challenge_period = challenge_period;
+        oracle_set.dispute_bond = dispute_bond;
+
+        emit!(DisputeWindowConfigured {
+            challenge_period,
+            dispute_bond,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank applying a pending progress report once it can no longer be challenged
+    pub fn finalize_progress_report(ctx: Context<FinalizeProgressReport>) -> Result<()> {
         require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
-        require!(ctx.accounts.ai_oracle.key() == ctx.accounts.platform_state.ai_oracle_authority, ErrorCode::UnauthorizedAIOracle);
-        require!(ai_confidence >= ctx.accounts.platform_state.min_ai_confidence, ErrorCode::InsufficientAIConfidence);
-        require!(satellite_verification_hash.len() == 64, ErrorCode::InvalidSatelliteHash);
 
+        let current_time = Clock::get()?.unix_timestamp;
+        let oracle_round = &mut ctx.accounts.oracle_round;
+        let pending = oracle_round.pending_progress.as_ref().ok_or(ErrorCode::NoPendingProgressReport)?;
+        require!(!pending.disputed, ErrorCode::ReportAlreadyDisputed);
+        require!(
+            pending.dispute_cleared
+                || current_time >= pending.submitted_at.saturating_add(ctx.accounts.oracle_set.challenge_period),
+            ErrorCode::ChallengePeriodActive
+        );
+
+        let pending = oracle_round.pending_progress.take().ok_or(ErrorCode::NoPendingProgressReport)?;
         let project = &mut ctx.accounts.project;
-        require!(trees_planted <= project.target_trees, ErrorCode::ExceedsTargetTrees);
-        require!(project.status == ProjectStatus::Active || project.status == ProjectStatus::Funded, ErrorCode::ProjectNotActive);
+        if project.status == ProjectStatus::Active || project.status == ProjectStatus::Funded {
+            apply_progress_update(project, &mut ctx.accounts.platform_state, pending.report, current_time)?;
+        } else {
+            // The project was suspended or closed while the report waited
+            emit!(ProgressReportDiscarded {
+                project_id: project.id,
+                round: pending.round,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Post a bonded challenge against a pending progress report
+    pub fn dispute_progress_report(ctx: Context<DisputeProgressReport>, evidence_hash: String) -> Result<()> {
+        require!(evidence_hash.len() == SATELLITE_HASH_LEN, ErrorCode::InvalidSatelliteHash);
+
+        let project = &ctx.accounts.project;
+        let challenger = ctx.accounts.challenger.key();
+        let is_investor = match &ctx.accounts.investment {
+            Some(investment) => {
+                investment.investor == challenger && investment.project_id == project.id && !investment.is_refunded
+            }
+            None => false,
+        };
+        require!(
+            challenger == project.creator || ctx.accounts.oracle_set.oracles.contains(&challenger) || is_investor,
+            ErrorCode::UnauthorizedChallenger
+        );
 
-        // Verify AI update interval
+        let oracle_round = &mut ctx.accounts.oracle_round;
+        let pending = oracle_round.pending_progress.as_mut().ok_or(ErrorCode::NoPendingProgressReport)?;
+        require!(!pending.disputed, ErrorCode::ReportAlreadyDisputed);
         let current_time = Clock::get()?.unix_timestamp;
         require!(
-            current_time - project.last_ai_update >= ctx.accounts.platform_state.ai_update_interval,
-            ErrorCode::TooFrequentAIUpdate
+            current_time < pending.submitted_at.saturating_add(ctx.accounts.oracle_set.challenge_period),
+            ErrorCode::ChallengePeriodEnded
         );
+        pending.disputed = true;
+        let round = pending.round;
 
-        // Update project with AI-verified data
-        project.trees_planted = trees_planted;
-        project.ai_health_score = ai_health_score;
-        project.ai_confidence = ai_confidence;
-        project.satellite_verification_hash = satellite_verification_hash.clone();
-        project.last_ai_update = current_time;
+        let bond = ctx.accounts.oracle_set.dispute_bond;
+        if bond > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.challenger_token_account.to_account_info(),
+                to: ctx.accounts.bond_vault.to_account_info(),
+                authority: ctx.accounts.challenger.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, bond)?;
+        }
 
-        // Update platform state
-        let platform_state = &mut ctx.accounts.platform_state;
-        platform_state.total_trees_planted = platform_state.total_trees_planted
-            .checked_add(trees_planted.saturating_sub(project.trees_planted))
-            .ok_or(ErrorCode::MathOverflow)?;
-        platform_state.total_co2_offset = platform_state.total_co2_offset
-            .checked_add(co2_sequestered)
-            .ok_or(ErrorCode::MathOverflow)?;
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.project = project.key();
+        dispute.round = round;
+        dispute.challenger = challenger;
+        dispute.bond = bond;
+        dispute.evidence_hash = evidence_hash;
+        dispute.created_at = current_time;
+        dispute.resolved = false;
+        dispute.challenger_won = false;
+        dispute.vault_bump = *ctx.bumps.get("bond_vault").unwrap();
+        dispute.bump = *ctx.bumps.get("dispute").unwrap();
+
+        emit!(ProgressReportDisputed {
+            project_id: project.id,
+            round,
+            challenger,
+            bond,
+        });
+
+        Ok(())
+    }
+
+    /// Settle a dispute: a winning challenger gets the bond back plus up to the bond slashed from each
+    /// submitting oracle's stake (stake and stake vault passed as remaining accounts per submitter) and
+    /// the report is dropped; otherwise the bond is split among the agreeing oracles (token accounts
+    /// passed as remaining accounts in submitter order) and the report may finalize immediately
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+        challenger_wins: bool,
+    ) -> Result<()> {
+        require!(!ctx.accounts.dispute.resolved, ErrorCode::DisputeAlreadyResolved);
+
+        let pending = ctx.accounts.oracle_round.pending_progress.clone().ok_or(ErrorCode::NoPendingProgressReport)?;
+        require!(pending.disputed && pending.round == ctx.accounts.dispute.round, ErrorCode::NoPendingProgressReport);
 
-        // Check if project is completed
-        if trees_planted >= project.target_trees {
-            project.status = ProjectStatus::Completed;
+        let dispute_key = ctx.accounts.dispute.key();
+        let dispute_seeds = &[
+            b"dispute".as_ref(),
+            ctx.accounts.dispute.project.as_ref(),
+            &ctx.accounts.dispute.round.to_le_bytes(),
+            &[ctx.accounts.dispute.bump],
+        ];
+        let signer_seeds = &[&dispute_seeds[..]];
+        let bond = ctx.accounts.dispute.bond;
+
+        let mut payouts: Vec<(AccountInfo<'info>, u64)> = Vec::new();
+        if challenger_wins {
+            payouts.push((ctx.accounts.challenger_token_account.to_account_info(), bond));
+        } else {
+            require!(
+                ctx.remaining_accounts.len() == pending.submitters.len(),
+                ErrorCode::InvalidBondRecipients
+            );
+            let share = bond / pending.submitters.len() as u64;
+            let dust = bond - share * pending.submitters.len() as u64;
+            for (index, (info, submitter)) in ctx.remaining_accounts.iter().zip(pending.submitters.iter()).enumerate() {
+                let recipient = Account::<TokenAccount>::try_from(info)?;
+                require!(
+                    recipient.owner == *submitter && recipient.mint == ctx.accounts.bond_vault.mint,
+                    ErrorCode::InvalidBondRecipients
+                );
+                payouts.push((info.clone(), if index == 0 { share + dust } else { share }));
+            }
         }
 
-        emit!(ProjectProgressUpdated {
-            project_id: project.id,
-            trees_planted,
-            ai_health_score,
-            ai_confidence,
-            satellite_hash: satellite_verification_hash,
-            co2_sequestered,
+        for (recipient, amount) in payouts {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: recipient,
+                authority: ctx.accounts.dispute.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        // Oracles behind an overturned report each forfeit up to the bond from their stake to the challenger.
+        // Remaining accounts are each submitter's stake and stake vault, in submitter order.
+        let mut slashed = 0u64;
+        if challenger_wins {
+            require!(
+                ctx.remaining_accounts.len() == pending.submitters.len() * 2,
+                ErrorCode::InvalidBondRecipients
+            );
+            for (accounts, submitter) in ctx.remaining_accounts.chunks(2).zip(pending.submitters.iter()) {
+                let (stake_key, _) = Pubkey::find_program_address(&[b"oracle_stake", submitter.as_ref()], ctx.program_id);
+                let (vault_key, _) = Pubkey::find_program_address(&[b"oracle_vault", submitter.as_ref()], ctx.program_id);
+                require!(
+                    accounts[0].key() == stake_key && accounts[1].key() == vault_key,
+                    ErrorCode::InvalidBondRecipients
+                );
+
+                let mut oracle_stake = Account::<OracleStake>::try_from(&accounts[0])?;
+                let stake_vault = Account::<TokenAccount>::try_from(&accounts[1])?;
+                let penalty = bond.min(oracle_stake.amount);
+                if penalty == 0 {
+                    continue;
+                }
+                slash_stake(
+                    &mut oracle_stake,
+                    &stake_vault,
+                    &ctx.accounts.challenger_token_account,
+                    &ctx.accounts.token_program,
+                    penalty,
+                    ctx.accounts.project.id,
+                )?;
+                oracle_stake.exit(ctx.program_id)?;
+                slashed += penalty;
+            }
+        }
+
+        let oracle_round = &mut ctx.accounts.oracle_round;
+        if challenger_wins {
+            oracle_round.pending_progress = None;
+        } else if let Some(pending) = oracle_round.pending_progress.as_mut() {
+            pending.disputed = false;
+            pending.dispute_cleared = true;
+        }
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.resolved = true;
+        dispute.challenger_won = challenger_wins;
+
+        emit!(DisputeResolved {
+            project_id: ctx.accounts.project.id,
+            round: dispute.round,
+            dispute: dispute_key,
+            challenger: dispute.challenger,
+            challenger_won: challenger_wins,
+            bond,
+            slashed,
         });
 
         Ok(())
     }
 
+    /// Release escrowed funds to the creator for every milestone reached since the last release
+    pub fn release_milestone_funds(ctx: Context<ReleaseMilestoneFunds>) -> Result<()> {
+        require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
+        require!(ctx.accounts.project.status != ProjectStatus::Suspended, ErrorCode::ProjectSuspended);
+        // Funds stay refundable until fundraising closes: fully funded, or expired past the minimum
+        require!(
+            matches!(ctx.accounts.project.status, ProjectStatus::Funded | ProjectStatus::Completed),
+            ErrorCode::FundraisingNotClosed
+        );
+
+        let project = &ctx.accounts.project;
+        let reached = project.milestones
+            .iter()
+            .take_while(|milestone| milestone.trees_target <= project.trees_planted)
+            .count();
+        require!(reached > project.milestones_released as usize, ErrorCode::NoMilestoneReached);
+
+        // Each tranche tops the creator up to the cumulative share unlocked by its milestone
+        let mut tranches: Vec<(u8, u64, u64)> = Vec::new();
+        let mut released = project.funds_released;
+        let mut unlocked_bps = project.milestones[..project.milestones_released as usize]
+            .iter()
+            .map(|milestone| milestone.release_bps as u128)
+            .sum::<u128>();
+        for index in project.milestones_released as usize..reached {
+            let milestone = &project.milestones[index];
+            unlocked_bps += milestone.release_bps as u128;
+            let amount = if unlocked_bps == 10000 {
+                // Final milestone sweeps whatever is left, including rounding dust
+                ctx.accounts.project_escrow.amount.saturating_sub(released - project.funds_released)
+            } else {
+                ((project.current_funding as u128 * unlocked_bps / 10000) as u64).saturating_sub(released)
+            };
+            released = released.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+            tranches.push((index as u8, milestone.trees_target, amount));
+        }
+
+        let total_amount = released - project.funds_released;
+        if total_amount > 0 {
+            let project_id_bytes = project.id.to_le_bytes();
+            let project_seeds = &[
+                b"project".as_ref(),
+                project_id_bytes.as_ref(),
+                &[project.bump],
+            ];
+            let signer_seeds = &[&project_seeds[..]];
+
+            let release_accounts = Transfer {
+                from: ctx.accounts.project_escrow.to_account_info(),
+                to: ctx.accounts.creator_token_account.to_account_info(),
+                authority: ctx.accounts.project.to_account_info(),
+            };
+            let release_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                release_accounts,
+                signer_seeds,
+            );
+            token::transfer(release_ctx, total_amount)?;
+        }
+
+        let project = &mut ctx.accounts.project;
+        project.milestones_released = reached as u8;
+        let mut running_total = project.funds_released;
+        project.funds_released = released;
+
+        for (milestone_index, trees_target, amount) in tranches {
+            running_total += amount;
+            emit!(MilestoneFundsReleased {
+                project_id: project.id,
+                milestone_index,
+                trees_target,
+                amount,
+                total_released: running_total,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Claim carbon credits with enhanced calculation based on AI health scores and investment tiers
     pub fn claim_carbon_credits(ctx: Context<ClaimCarbonCredits>) -> Result<()> {
         require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
-        require!(ctx.accounts.project.status == ProjectStatus::Completed, ErrorCode::ProjectNotCompleted);
+        require!(
+            matches!(
+                ctx.accounts.project.status,
+                ProjectStatus::Active | ProjectStatus::Funded | ProjectStatus::Completed
+            ),
+            ErrorCode::ProjectNotVesting
+        );
         require!(ctx.accounts.investment.is_active, ErrorCode::InvestmentNotActive);
+        require!(!ctx.accounts.investment.is_refunded, ErrorCode::InvestmentRefunded);
         require!(ctx.accounts.investment.project_id == ctx.accounts.project.id, ErrorCode::ProjectMismatch);
+        // Pending reversal debits come out of unclaimed credits, so they land before anything is paid
+        if ctx.accounts.investment.reversals_applied < ctx.accounts.project.reversal_count {
+            let investment_key = ctx.accounts.investment.key();
+            debit_investment_reversal(&mut ctx.accounts.project, &mut ctx.accounts.investment, investment_key);
+        }
 
+        let (total_credits, bonus_credits) = settle_claim(&mut ctx.accounts.project, &mut ctx.accounts.investment);
+        require!(total_credits > 0, ErrorCode::NoVerifiedCredits);
         let project = &ctx.accounts.project;
-        let investment = &mut ctx.accounts.investment;
 
-        // Enhanced carbon credit calculation
-        let base_credits = (investment.amount * project.expected_co2_per_tree as u64) / 1_000_000; // Base calculation
-        
-        // AI health score multiplier (80-100% -> 0.8-1.2x)
-        let health_multiplier = (project.ai_health_score as u64 * 4 + 480) / 1000; // Maps 80-100 to 800-880, then /1000 for 0.8-0.88
-        
-        // Investment tier bonus
-        let tier_multiplier = investment.bonus_multiplier as u64;
-        
-        // Final calculation with all bonuses
-        let total_credits = (base_credits * health_multiplier * tier_multiplier) / 10000;
+        // Part of the allocation is held in the project's buffer, more for riskier projects
+        let buffer_bps = (ctx.accounts.platform_state.buffer_bps as u64 * (100 + project.ai_risk_assessment as u64) / 100)
+            .min(10000);
+        let buffer_credits = total_credits * buffer_bps / 10000;
+        let investor_credits = total_credits - buffer_credits;
 
         // Mint carbon credits to investor
         let mint_accounts = MintTo {
@@ -309,11 +825,58 @@ pub mod greenchain_reforestation {
             mint_accounts,
             signer_seeds,
         );
-        token::mint_to(mint_ctx, total_credits)?;
+        token::mint_to(mint_ctx, investor_credits)?;
+
+        if buffer_credits > 0 {
+            let buffer_accounts = MintTo {
+                mint: ctx.accounts.carbon_token_mint.to_account_info(),
+                to: ctx.accounts.buffer_account.to_account_info(),
+                authority: ctx.accounts.platform_state.to_account_info(),
+            };
+            let buffer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                buffer_accounts,
+                signer_seeds,
+            );
+            token::mint_to(buffer_ctx, buffer_credits)?;
+        }
+
+        // Every issuance becomes a serialized batch, tagged with the vintage of the latest verification
+        let platform_state = &mut ctx.accounts.platform_state;
+        let serial_start = platform_state.next_credit_serial;
+        platform_state.next_credit_serial = serial_start
+            .checked_add(total_credits)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let project = &ctx.accounts.project;
+        let batch = &mut ctx.accounts.credit_batch;
+        batch.project_id = project.id;
+        batch.mint = ctx.accounts.carbon_token_mint.key();
+        batch.index = project.credit_batch_count;
+        batch.vintage_year = vintage_year(project.last_ai_update);
+        batch.serial_start = serial_start;
+        batch.serial_end = serial_start + total_credits - 1;
+        batch.amount = total_credits;
+        batch.buffer_amount = buffer_credits;
+        batch.retired = 0;
+        batch.methodology = project.methodology.clone();
+        batch.verification_hash = project.satellite_verification_hash.clone();
+        batch.issued_at = Clock::get()?.unix_timestamp;
+        batch.bump = *ctx.bumps.get("credit_batch").unwrap();
+
+        let holding = &mut ctx.accounts.batch_holding;
+        holding.batch = ctx.accounts.credit_batch.key();
+        holding.owner = ctx.accounts.investor.key();
+        holding.amount = investor_credits;
+        holding.bump = *ctx.bumps.get("batch_holding").unwrap();
 
         // Update records
-        investment.carbon_credits_earned = total_credits;
-        investment.is_active = false; // Mark as claimed
+        let investment = &mut ctx.accounts.investment;
+        investment.carbon_credits_earned += investor_credits;
+
+        let project = &mut ctx.accounts.project;
+        project.credit_batch_count += 1;
+        project.buffer_credits += buffer_credits;
 
         let platform_state = &mut ctx.accounts.platform_state;
         platform_state.total_carbon_credits = platform_state.total_carbon_credits
@@ -323,368 +886,4186 @@ pub mod greenchain_reforestation {
         emit!(CarbonCreditsClaimed {
             user: ctx.accounts.investor.key(),
             project_id: project.id,
-            amount: total_credits,
+            amount: investor_credits,
             total_offset: platform_state.total_carbon_credits,
             investment_tier: investment.investment_tier,
-            ai_health_bonus: health_multiplier,
+            bonus_credits,
+            buffer_credits,
+            batch: ctx.accounts.credit_batch.key(),
+            serial_start: ctx.accounts.credit_batch.serial_start,
+            serial_end: ctx.accounts.credit_batch.serial_end,
         });
 
         Ok(())
     }
 
-    /// Emergency pause function for security
-    pub fn emergency_pause(ctx: Context<EmergencyAction>) -> Result<()> {
-        require!(ctx.accounts.authority.key() == ctx.accounts.platform_state.authority, ErrorCode::UnauthorizedEmergency);
-        
-        let platform_state = &mut ctx.accounts.platform_state;
-        platform_state.emergency_pause = true;
+    /// Transfer carbon credits out of a batch holding, keeping the batch provenance with the tokens
+    pub fn transfer_credits(ctx: Context<TransferCredits>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
+        require!(amount > 0, ErrorCode::InvalidTransferAmount);
+        require!(ctx.accounts.sender_holding.amount >= amount, ErrorCode::InsufficientBatchBalance);
 
-        emit!(EmergencyPause {
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.sender_carbon_account.to_account_info(),
+            to: ctx.accounts.recipient_carbon_account.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts);
+        token::transfer(transfer_ctx, amount)?;
 
-        Ok(())
-    }
+        ctx.accounts.sender_holding.amount -= amount;
 
-    /// Emergency resume function
-    pub fn emergency_resume(ctx: Context<EmergencyAction>) -> Result<()> {
-        require!(ctx.accounts.authority.key() == ctx.accounts.platform_state.authority, ErrorCode::UnauthorizedEmergency);
-        
-        let platform_state = &mut ctx.accounts.platform_state;
-        platform_state.emergency_pause = false;
+        let recipient_holding = &mut ctx.accounts.recipient_holding;
+        if recipient_holding.owner == Pubkey::default() {
+            recipient_holding.batch = ctx.accounts.credit_batch.key();
+            recipient_holding.owner = ctx.accounts.recipient.key();
+            recipient_holding.bump = *ctx.bumps.get("recipient_holding").unwrap();
+        }
+        recipient_holding.amount = recipient_holding.amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
 
-        emit!(EmergencyResume {
-            timestamp: Clock::get()?.unix_timestamp,
+        emit!(CreditsTransferred {
+            batch: ctx.accounts.credit_batch.key(),
+            project_id: ctx.accounts.credit_batch.project_id,
+            vintage_year: ctx.accounts.credit_batch.vintage_year,
+            from: ctx.accounts.sender.key(),
+            to: ctx.accounts.recipient.key(),
+            amount,
         });
 
         Ok(())
     }
 
-    /// Update AI oracle authority
-    pub fn update_ai_oracle(
-        ctx: Context<UpdateAIOracle>,
-        new_ai_oracle: Pubkey,
+    /// Burn carbon credits on behalf of a beneficiary and record a retirement certificate
+    pub fn retire_credits(
+        ctx: Context<RetireCredits>,
+        amount: u64,
+        beneficiary_name: String,
+        reason: String,
     ) -> Result<()> {
-        require!(ctx.accounts.authority.key() == ctx.accounts.platform_state.authority, ErrorCode::UnauthorizedEmergency);
-        
+        require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
+        require!(amount > 0, ErrorCode::InvalidRetirementAmount);
+        require!(beneficiary_name.len() <= MAX_BENEFICIARY_LEN, ErrorCode::BeneficiaryNameTooLong);
+        require!(reason.len() <= MAX_RETIREMENT_REASON_LEN, ErrorCode::RetirementReasonTooLong);
+        require!(ctx.accounts.batch_holding.amount >= amount, ErrorCode::InsufficientBatchBalance);
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.carbon_token_mint.to_account_info(),
+            from: ctx.accounts.holder_carbon_account.to_account_info(),
+            authority: ctx.accounts.holder.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        token::burn(burn_ctx, amount)?;
+
+        // Retired serials are consumed from the front of the batch's outstanding range
+        ctx.accounts.batch_holding.amount -= amount;
+        let batch = &mut ctx.accounts.credit_batch;
+        require!(
+            batch.retired + amount <= batch.amount - batch.buffer_amount,
+            ErrorCode::InsufficientBatchBalance
+        );
+        let serial_start = batch.serial_start + batch.retired;
+        batch.retired += amount;
+
         let platform_state = &mut ctx.accounts.platform_state;
-        platform_state.ai_oracle_authority = new_ai_oracle;
+        let sequence = platform_state.total_retirements;
+        platform_state.total_retirements += 1;
+        platform_state.total_credits_retired = platform_state.total_credits_retired
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
 
-        emit!(AIOracleUpdated {
-            old_oracle: ctx.accounts.platform_state.ai_oracle_authority,
-            new_oracle: new_ai_oracle,
+        let certificate = &mut ctx.accounts.certificate;
+        certificate.sequence = sequence;
+        certificate.retiree = ctx.accounts.holder.key();
+        certificate.beneficiary_name = beneficiary_name;
+        certificate.reason = reason;
+        certificate.project_id = batch.project_id;
+        certificate.batch = batch.key();
+        certificate.vintage_year = batch.vintage_year;
+        certificate.serial_start = serial_start;
+        certificate.serial_end = serial_start + amount - 1;
+        certificate.amount = amount;
+        certificate.timestamp = Clock::get()?.unix_timestamp;
+        certificate.bump = *ctx.bumps.get("certificate").unwrap();
+
+        emit!(CreditsRetired {
+            sequence,
+            retiree: certificate.retiree,
+            beneficiary_name: certificate.beneficiary_name.clone(),
+            project_id: certificate.project_id,
+            batch: certificate.batch,
+            serial_start: certificate.serial_start,
+            serial_end: certificate.serial_end,
+            amount,
+            total_retired: platform_state.total_credits_retired,
         });
 
         Ok(())
     }
 
-    /// Anomaly detection and automated response
-    pub fn report_anomaly(
-        ctx: Context<ReportAnomaly>,
-        project_id: u64,
-        anomaly_type: AnomalyType,
-        severity: u8,
-        ai_confidence: u8,
-        satellite_evidence_hash: String,
+    /// Claw back credits for tonnes lost to a fire or deforestation: the buffer is burned first,
+    /// then the remainder is debited pro-rata from the unclaimed balances of the investments
+    /// passed as remaining accounts (more can follow through apply_reversal_debits)
+    pub fn resolve_reversal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveReversal<'info>>,
+        tonnes_lost: u64,
     ) -> Result<()> {
-        require!(ctx.accounts.ai_oracle.key() == ctx.accounts.platform_state.ai_oracle_authority, ErrorCode::UnauthorizedAIOracle);
-        require!(ai_confidence >= 90, ErrorCode::InsufficientAIConfidence); // High confidence required for anomalies
-        require!(severity <= 100, ErrorCode::InvalidSeverity);
-        require!(satellite_evidence_hash.len() == 64, ErrorCode::InvalidSatelliteHash);
+        require!(tonnes_lost > 0, ErrorCode::InvalidCancellationAmount);
+        // Can't lose more than the project has verified
+        require!(tonnes_lost <= ctx.accounts.project.co2_sequestered, ErrorCode::ReversalExceedsSequestered);
+        let anomaly_type = reversal_anomaly_type(&ctx.accounts.anomaly)?;
+        // Each reversal's investor loss is tracked on its own, so the previous one must reach every investment first
+        require!(
+            ctx.accounts.project.reversal_investors_applied == ctx.accounts.project.total_investors,
+            ErrorCode::ReversalPending
+        );
 
-        let project = &mut ctx.accounts.project;
-        require!(project.id == project_id, ErrorCode::ProjectMismatch);
+        let buffer_debit = tonnes_lost
+            .min(ctx.accounts.project.buffer_credits)
+            .min(ctx.accounts.buffer_account.amount);
+        if buffer_debit > 0 {
+            let platform_seeds = &[
+                b"platform_state".as_ref(),
+                &[ctx.accounts.platform_state.bump],
+            ];
+            let signer_seeds = &[&platform_seeds[..]];
 
-        // Automated response based on severity
-        if severity >= 80 {
-            project.status = ProjectStatus::Suspended; // Auto-suspend high-severity anomalies
-        } else if severity >= 50 {
-            project.ai_health_score = project.ai_health_score.saturating_sub(severity / 2); // Reduce health score
+            let burn_accounts = Burn {
+                mint: ctx.accounts.carbon_token_mint.to_account_info(),
+                from: ctx.accounts.buffer_account.to_account_info(),
+                authority: ctx.accounts.platform_state.to_account_info(),
+            };
+            let burn_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                burn_accounts,
+                signer_seeds,
+            );
+            token::burn(burn_ctx, buffer_debit)?;
         }
 
-        emit!(AnomalyDetected {
-            project_id,
-            anomaly_type,
-            severity,
-            ai_confidence,
-            automated_action: if severity >= 80 { "suspended".to_string() } else { "health_reduced".to_string() },
-            satellite_evidence: satellite_evidence_hash,
-        });
+        ctx.accounts.anomaly.reversal_applied = true;
+        let project = &mut ctx.accounts.project;
+        project.buffer_credits -= buffer_debit;
+        project.buffer_cancelled += buffer_debit;
+        project.carbon_credits_minted -= buffer_debit;
+        project.co2_accrued = project.co2_accrued.saturating_sub(buffer_debit);
+        project.total_reversed += buffer_debit;
 
-        Ok(())
-    }
-}
+        // Whatever the buffer couldn't cover is owed by investors' unclaimed credits
+        project.reversal_count += 1;
+        project.reversal_investor_loss = tonnes_lost - buffer_debit;
+        project.reversal_debited = 0;
+        project.reversal_investors_applied = 0;
+
+        project.co2_sequestered -= tonnes_lost;
+
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.total_carbon_credits = platform_state.total_carbon_credits.saturating_sub(buffer_debit);
+        platform_state.total_co2_offset = platform_state.total_co2_offset.saturating_sub(tonnes_lost);
+
+        emit!(ReversalResolved {
+            project_id: project.id,
+            reversal: project.reversal_count,
+            anomaly: ctx.accounts.anomaly.key(),
+            anomaly_type,
+            tonnes_lost,
+            buffer_debit,
+            investor_loss: project.reversal_investor_loss,
+        });
+
+        debit_investor_reversals(project, ctx.remaining_accounts)
+    }
+
+    /// Debit the latest reversal from further investments, for projects with too many to fit in one transaction;
+    /// anyone can run it since the debit follows entirely from on-chain state
+    pub fn apply_reversal_debits<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApplyReversalDebits<'info>>,
+    ) -> Result<()> {
+        require!(ctx.accounts.project.reversal_count > 0, ErrorCode::NoReversalAnomaly);
+        debit_investor_reversals(&mut ctx.accounts.project, ctx.remaining_accounts)
+    }
+
+    /// Declare a project failed, freezing the remaining escrow for investor refunds
+    pub fn declare_project_failed(ctx: Context<DeclareProjectFailed>) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status != ProjectStatus::Completed
+                && project.status != ProjectStatus::Failed
+                && project.status != ProjectStatus::Rejected,
+            ErrorCode::ProjectNotActive
+        );
+
+        mark_project_failed(project, ctx.accounts.project_escrow.amount)
+    }
+
+    /// Permissionless crank closing fundraising once the deadline has passed
+    pub fn expire_project(ctx: Context<ExpireProject>) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active || project.status == ProjectStatus::PendingReview,
+            ErrorCode::ProjectNotActive
+        );
+        require!(Clock::get()?.unix_timestamp > project.funding_deadline, ErrorCode::FundingDeadlineNotReached);
+
+        // Projects that cleared the minimum threshold proceed with what they raised;
+        // one still awaiting review never opened for funding, so it always fails
+        let min_funding = (project.target_funding as u128 * project.min_funding_bps as u128 / 10000) as u64;
+        let reached_minimum = project.status == ProjectStatus::Active && project.current_funding >= min_funding;
+
+        emit!(ProjectExpired {
+            project_id: project.id,
+            current_funding: project.current_funding,
+            min_funding,
+            failed: !reached_minimum,
+        });
+
+        if reached_minimum {
+            project.status = ProjectStatus::Funded;
+            Ok(())
+        } else {
+            mark_project_failed(project, ctx.accounts.project_escrow.amount)
+        }
+    }
+
+    /// Return an investor's pro-rata share of the escrow left in a failed project
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
+        require!(ctx.accounts.project.status == ProjectStatus::Failed, ErrorCode::ProjectNotFailed);
+        require!(!ctx.accounts.investment.is_refunded, ErrorCode::InvestmentRefunded);
+        require!(ctx.accounts.investment.project_id == ctx.accounts.project.id, ErrorCode::ProjectMismatch);
+
+        let project = &ctx.accounts.project;
+        let refund_amount = if project.current_funding == 0 {
+            0
+        } else {
+            (ctx.accounts.investment.amount as u128 * project.refund_pool as u128
+                / project.current_funding as u128) as u64
+        };
+
+        if refund_amount > 0 {
+            let project_id_bytes = project.id.to_le_bytes();
+            let project_seeds = &[
+                b"project".as_ref(),
+                project_id_bytes.as_ref(),
+                &[project.bump],
+            ];
+            let signer_seeds = &[&project_seeds[..]];
+
+            let refund_accounts = Transfer {
+                from: ctx.accounts.project_escrow.to_account_info(),
+                to: ctx.accounts.investor_token_account.to_account_info(),
+                authority: ctx.accounts.project.to_account_info(),
+            };
+            let refund_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                refund_accounts,
+                signer_seeds,
+            );
+            token::transfer(refund_ctx, refund_amount)?;
+        }
+
+        let investment = &mut ctx.accounts.investment;
+        investment.is_refunded = true;
+        investment.is_active = false;
+
+        emit!(RefundClaimed {
+            project_id: ctx.accounts.project.id,
+            investor: ctx.accounts.investor.key(),
+            invested_amount: investment.amount,
+            refund_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Emergency pause function for security
+    pub fn emergency_pause(ctx: Context<EmergencyAction>) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.emergency_pause = true;
+
+        emit!(EmergencyPause {
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Emergency resume function
+    pub fn emergency_resume(ctx: Context<EmergencyAction>) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.emergency_pause = false;
+
+        emit!(EmergencyResume {
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Propose a new platform authority; takes effect only once the new key accepts
+    pub fn propose_authority(ctx: Context<ManageAuthority>, new_authority: Pubkey) -> Result<()> {
+        require!(ctx.accounts.authority.key() == ctx.accounts.platform_state.authority, ErrorCode::UnauthorizedAuthority);
+        require!(new_authority != Pubkey::default(), ErrorCode::InvalidAuthority);
+
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.pending_authority = new_authority;
+
+        emit!(AuthorityTransferProposed {
+            authority: platform_state.authority,
+            proposed_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer; must be signed by the proposed key
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        require!(platform_state.pending_authority != Pubkey::default(), ErrorCode::NoPendingAuthority);
+        require!(ctx.accounts.new_authority.key() == platform_state.pending_authority, ErrorCode::InvalidAuthority);
+
+        let old_authority = platform_state.authority;
+        platform_state.authority = platform_state.pending_authority;
+        platform_state.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: platform_state.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw a pending authority transfer
+    pub fn cancel_authority_transfer(ctx: Context<ManageAuthority>) -> Result<()> {
+        require!(ctx.accounts.authority.key() == ctx.accounts.platform_state.authority, ErrorCode::UnauthorizedAuthority);
+
+        let platform_state = &mut ctx.accounts.platform_state;
+        require!(platform_state.pending_authority != Pubkey::default(), ErrorCode::NoPendingAuthority);
+        let cancelled_authority = platform_state.pending_authority;
+        platform_state.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferCancelled {
+            authority: platform_state.authority,
+            cancelled_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Queue a change to fees and AI thresholds; executable only after the timelock
+    pub fn update_platform_config(
+        ctx: Context<ConfigAdminAction>,
+        investment_fee_bps: u16,
+        min_ai_confidence: u8,
+        ai_update_interval: i64,
+        buffer_bps: u16,
+    ) -> Result<()> {
+        queue_config_update(
+            &mut ctx.accounts.platform_state,
+            investment_fee_bps,
+            min_ai_confidence,
+            ai_update_interval,
+            buffer_bps,
+        )
+    }
+
+    /// Apply a queued config change once its timelock has elapsed; callable by anyone
+    pub fn execute_platform_config_update(ctx: Context<ExecuteConfigUpdate>) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        let pending = platform_state.pending_config.ok_or(ErrorCode::NoPendingConfigUpdate)?;
+        require!(Clock::get()?.unix_timestamp >= pending.executable_at, ErrorCode::ConfigTimelockActive);
+
+        emit!(PlatformConfigUpdated {
+            old_investment_fee_bps: platform_state.investment_fee_bps,
+            new_investment_fee_bps: pending.investment_fee_bps,
+            old_min_ai_confidence: platform_state.min_ai_confidence,
+            new_min_ai_confidence: pending.min_ai_confidence,
+            old_ai_update_interval: platform_state.ai_update_interval,
+            new_ai_update_interval: pending.ai_update_interval,
+            old_buffer_bps: platform_state.buffer_bps,
+            new_buffer_bps: pending.buffer_bps,
+        });
+
+        platform_state.investment_fee_bps = pending.investment_fee_bps;
+        platform_state.min_ai_confidence = pending.min_ai_confidence;
+        platform_state.ai_update_interval = pending.ai_update_interval;
+        platform_state.buffer_bps = pending.buffer_bps;
+        platform_state.pending_config = None;
+
+        Ok(())
+    }
+
+    /// Drop a queued config change before it executes
+    pub fn cancel_platform_config_update(ctx: Context<ConfigAdminAction>) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        let pending = platform_state.pending_config.take().ok_or(ErrorCode::NoPendingConfigUpdate)?;
+
+        emit!(ConfigUpdateCancelled {
+            investment_fee_bps: pending.investment_fee_bps,
+            min_ai_confidence: pending.min_ai_confidence,
+            ai_update_interval: pending.ai_update_interval,
+            buffer_bps: pending.buffer_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Grant an operational role to a key; super-admin only
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+        require!(ctx.accounts.authority.key() == ctx.accounts.platform_state.authority, ErrorCode::UnauthorizedAuthority);
+
+        let role_assignment = &mut ctx.accounts.role_assignment;
+        role_assignment.holder = holder;
+        role_assignment.role = role;
+        role_assignment.granted_by = ctx.accounts.authority.key();
+        role_assignment.granted_at = Clock::get()?.unix_timestamp;
+        role_assignment.bump = *ctx.bumps.get("role_assignment").unwrap();
+
+        emit!(RoleGranted {
+            role,
+            holder,
+            granted_by: role_assignment.granted_by,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a role by closing its assignment account; super-admin only
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        require!(ctx.accounts.authority.key() == ctx.accounts.platform_state.authority, ErrorCode::UnauthorizedAuthority);
+
+        emit!(RoleRevoked {
+            role: ctx.accounts.role_assignment.role,
+            holder: ctx.accounts.role_assignment.holder,
+            revoked_by: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Create the M-of-N multisig that can hold the platform authority
+    pub fn create_multisig(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(ctx.accounts.authority.key() == ctx.accounts.platform_state.authority, ErrorCode::UnauthorizedAuthority);
+        require!(!signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS, ErrorCode::InvalidMultisig);
+        require!(threshold > 0 && threshold as usize <= signers.len(), ErrorCode::InvalidMultisig);
+        for (index, signer) in signers.iter().enumerate() {
+            require!(!signers[..index].contains(signer), ErrorCode::InvalidMultisig);
+        }
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+        multisig.proposal_count = 0;
+        multisig.bump = *ctx.bumps.get("multisig").unwrap();
+
+        emit!(MultisigCreated {
+            multisig: multisig.key(),
+            signers: multisig.signers.clone(),
+            threshold,
+        });
+
+        Ok(())
+    }
+
+    /// Propose an admin action for the multisig; the proposer's approval is recorded
+    pub fn propose_admin_action(ctx: Context<ProposeAdminAction>, action: AdminAction) -> Result<()> {
+        let proposer = ctx.accounts.proposer.key();
+        let multisig = &mut ctx.accounts.multisig;
+        require!(multisig.signers.contains(&proposer), ErrorCode::NotMultisigSigner);
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.multisig = multisig.key();
+        proposal.id = multisig.proposal_count;
+        proposal.proposer = proposer;
+        proposal.action = action;
+        proposal.approvals = vec![proposer];
+        proposal.executed = false;
+        proposal.created_at = Clock::get()?.unix_timestamp;
+        proposal.bump = *ctx.bumps.get("proposal").unwrap();
+
+        multisig.proposal_count += 1;
+
+        emit!(ProposalCreated {
+            proposal_id: proposal.id,
+            proposer,
+            action,
+        });
+
+        Ok(())
+    }
+
+    /// Approve a pending multisig proposal
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let approver = ctx.accounts.approver.key();
+        require!(ctx.accounts.multisig.signers.contains(&approver), ErrorCode::NotMultisigSigner);
+
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+        require!(!proposal.approvals.contains(&approver), ErrorCode::AlreadyApproved);
+        proposal.approvals.push(approver);
+
+        emit!(ProposalApproved {
+            proposal_id: proposal.id,
+            approver,
+            approvals: proposal.approvals.len() as u8,
+        });
+
+        Ok(())
+    }
+
+    /// Execute a proposal that has reached the multisig threshold
+    pub fn execute_proposal<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let executor = ctx.accounts.executor.key();
+        require!(multisig.signers.contains(&executor), ErrorCode::NotMultisigSigner);
+
+        // Signers removed since approving no longer count towards the threshold
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|approver| multisig.signers.contains(approver))
+            .count();
+        require!(approvals >= multisig.threshold as usize, ErrorCode::InsufficientApprovals);
+
+        let platform_state = &mut ctx.accounts.platform_state;
+        let multisig_key = multisig.key();
+        match proposal.action {
+            AdminAction::AcceptAuthority => {
+                require!(platform_state.pending_authority == multisig_key, ErrorCode::InvalidAuthority);
+            }
+            _ => {
+                require!(platform_state.authority == multisig_key, ErrorCode::UnauthorizedAuthority);
+            }
+        }
+
+        match proposal.action {
+            AdminAction::Pause => {
+                platform_state.emergency_pause = true;
+                emit!(EmergencyPause {
+                    timestamp: Clock::get()?.unix_timestamp,
+                });
+            }
+            AdminAction::Resume => {
+                platform_state.emergency_pause = false;
+                emit!(EmergencyResume {
+                    timestamp: Clock::get()?.unix_timestamp,
+                });
+            }
+            AdminAction::UpdateAIOracle { new_oracle } => {
+                let old_oracle = platform_state.ai_oracle_authority;
+                platform_state.ai_oracle_authority = new_oracle;
+                emit!(AIOracleUpdated {
+                    old_oracle,
+                    new_oracle,
+                });
+            }
+            AdminAction::QueueConfigUpdate {
+                investment_fee_bps,
+                min_ai_confidence,
+                ai_update_interval,
+                buffer_bps,
+            } => {
+                queue_config_update(
+                    platform_state,
+                    investment_fee_bps,
+                    min_ai_confidence,
+                    ai_update_interval,
+                    buffer_bps,
+                )?;
+            }
+            AdminAction::ProposeAuthority { new_authority } => {
+                require!(new_authority != Pubkey::default(), ErrorCode::InvalidAuthority);
+                platform_state.pending_authority = new_authority;
+                emit!(AuthorityTransferProposed {
+                    authority: platform_state.authority,
+                    proposed_authority: new_authority,
+                });
+            }
+            AdminAction::AcceptAuthority => {
+                let old_authority = platform_state.authority;
+                platform_state.authority = multisig_key;
+                platform_state.pending_authority = Pubkey::default();
+                emit!(AuthorityTransferred {
+                    old_authority,
+                    new_authority: multisig_key,
+                });
+            }
+            AdminAction::WithdrawFees { mint, amount } => {
+                // Remaining accounts: fee vault, fee split, token program, then the split's recipients
+                let [fee_vault_info, fee_split_info, token_program_info, recipients @ ..] = ctx.remaining_accounts else {
+                    return err!(ErrorCode::InvalidFeeRecipients);
+                };
+                let (fee_vault_key, _) = Pubkey::find_program_address(&[b"fee_vault", mint.as_ref()], ctx.program_id);
+                let (fee_split_key, _) = Pubkey::find_program_address(&[b"fee_split"], ctx.program_id);
+                require!(fee_vault_info.key() == fee_vault_key, ErrorCode::InvalidFeeRecipients);
+                require!(fee_split_info.key() == fee_split_key, ErrorCode::InvalidFeeRecipients);
+                require!(token_program_info.key() == token::ID, ErrorCode::InvalidFeeRecipients);
+
+                let fee_vault = Account::<TokenAccount>::try_from(fee_vault_info)?;
+                let fee_split = Account::<FeeSplit>::try_from(fee_split_info)?;
+                distribute_fees(platform_state, &fee_vault, &fee_split, token_program_info, recipients, amount, multisig_key)?;
+            }
+            AdminAction::GrantRole { role, holder } => {
+                // Remaining accounts: the role assignment PDA to create, funded by the executor
+                let role_info = ctx.remaining_accounts.first().ok_or(ErrorCode::InvalidRoleAccount)?;
+                let role_seed = [role as u8];
+                let (role_key, role_bump) =
+                    Pubkey::find_program_address(&[b"role", role_seed.as_ref(), holder.as_ref()], ctx.program_id);
+                require!(role_info.key() == role_key, ErrorCode::InvalidRoleAccount);
+
+                let space = 8 + std::mem::size_of::<RoleAssignment>();
+                let role_seeds = &[b"role".as_ref(), role_seed.as_ref(), holder.as_ref(), &[role_bump]];
+                let create_accounts = system_program::CreateAccount {
+                    from: ctx.accounts.executor.to_account_info(),
+                    to: role_info.clone(),
+                };
+                let signer_seeds = &[&role_seeds[..]];
+                let create_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    create_accounts,
+                    signer_seeds,
+                );
+                system_program::create_account(create_ctx, Rent::get()?.minimum_balance(space), space as u64, ctx.program_id)?;
+
+                let role_assignment = RoleAssignment {
+                    holder,
+                    role,
+                    granted_by: multisig_key,
+                    granted_at: Clock::get()?.unix_timestamp,
+                    bump: role_bump,
+                };
+                role_assignment.try_serialize(&mut &mut role_info.try_borrow_mut_data()?[..])?;
+
+                emit!(RoleGranted {
+                    role,
+                    holder,
+                    granted_by: multisig_key,
+                });
+            }
+            AdminAction::RevokeRole { role, holder } => {
+                // Remaining accounts: the role assignment PDA to close; its rent goes to the executor
+                let role_info = ctx.remaining_accounts.first().ok_or(ErrorCode::InvalidRoleAccount)?;
+                let role_assignment = Account::<RoleAssignment>::try_from(role_info)?;
+                require!(
+                    role_assignment.role == role && role_assignment.holder == holder,
+                    ErrorCode::InvalidRoleAccount
+                );
+                role_assignment.close(ctx.accounts.executor.to_account_info())?;
+
+                emit!(RoleRevoked {
+                    role,
+                    holder,
+                    revoked_by: multisig_key,
+                });
+            }
+            AdminAction::AddSigner { signer } => {
+                let multisig = &mut ctx.accounts.multisig;
+                require!(!multisig.signers.contains(&signer), ErrorCode::InvalidMultisig);
+                require!(multisig.signers.len() < MAX_MULTISIG_SIGNERS, ErrorCode::InvalidMultisig);
+                multisig.signers.push(signer);
+                emit!(MultisigUpdated {
+                    multisig: multisig_key,
+                    signers: multisig.signers.clone(),
+                    threshold: multisig.threshold,
+                });
+            }
+            AdminAction::RemoveSigner { signer } => {
+                let multisig = &mut ctx.accounts.multisig;
+                require!(multisig.signers.contains(&signer), ErrorCode::NotMultisigSigner);
+                multisig.signers.retain(|existing| *existing != signer);
+                require!(multisig.signers.len() >= multisig.threshold as usize, ErrorCode::InvalidMultisig);
+                emit!(MultisigUpdated {
+                    multisig: multisig_key,
+                    signers: multisig.signers.clone(),
+                    threshold: multisig.threshold,
+                });
+            }
+            AdminAction::ChangeThreshold { threshold } => {
+                let multisig = &mut ctx.accounts.multisig;
+                require!(
+                    threshold > 0 && threshold as usize <= multisig.signers.len(),
+                    ErrorCode::InvalidMultisig
+                );
+                multisig.threshold = threshold;
+                emit!(MultisigUpdated {
+                    multisig: multisig_key,
+                    signers: multisig.signers.clone(),
+                    threshold,
+                });
+            }
+        }
+
+        proposal.executed = true;
+
+        emit!(ProposalExecuted {
+            proposal_id: proposal.id,
+            executor,
+            action: proposal.action,
+        });
+
+        Ok(())
+    }
+
+    /// Set how withdrawn platform fees are split across recipients, in basis points summing to 100%
+    pub fn configure_fee_split(ctx: Context<ConfigureFeeSplit>, recipients: Vec<FeeRecipient>) -> Result<()> {
+        require!(
+            !recipients.is_empty() && recipients.len() <= MAX_FEE_RECIPIENTS,
+            ErrorCode::InvalidFeeSplit
+        );
+        let total_bps: u32 = recipients.iter().map(|recipient| recipient.bps as u32).sum();
+        require!(total_bps == 10000, ErrorCode::InvalidFeeSplit);
+        for (index, recipient) in recipients.iter().enumerate() {
+            require!(recipient.bps > 0, ErrorCode::InvalidFeeSplit);
+            require!(
+                !recipients[..index].iter().any(|other| other.wallet == recipient.wallet),
+                ErrorCode::InvalidFeeSplit
+            );
+        }
+
+        let fee_split = &mut ctx.accounts.fee_split;
+        fee_split.recipients = recipients;
+        fee_split.bump = *ctx.bumps.get("fee_split").unwrap();
+
+        emit!(FeeSplitConfigured {
+            recipients: fee_split.recipients.iter().map(|recipient| recipient.wallet).collect(),
+            bps: fee_split.recipients.iter().map(|recipient| recipient.bps).collect(),
+        });
+
+        Ok(())
+    }
+
+    /// Pay out platform fees from the fee vault according to the fee split. Recipient token
+    /// accounts are passed as remaining accounts in the same order as the split
+    pub fn withdraw_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawFees<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        distribute_fees(
+            &ctx.accounts.platform_state,
+            &ctx.accounts.fee_vault,
+            &ctx.accounts.fee_split,
+            &ctx.accounts.token_program.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.treasurer.key(),
+        )
+    }
+
+    /// Update AI oracle authority
+    pub fn update_ai_oracle(
+        ctx: Context<UpdateAIOracle>,
+        new_ai_oracle: Pubkey,
+    ) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        let old_oracle = platform_state.ai_oracle_authority;
+        platform_state.ai_oracle_authority = new_ai_oracle;
+
+        emit!(AIOracleUpdated {
+            old_oracle,
+            new_oracle: new_ai_oracle,
+        });
+
+        Ok(())
+    }
+
+    /// Configure the oracle set whose quorum drives progress and anomaly reports
+    pub fn initialize_oracle_set(
+        ctx: Context<InitializeOracleSet>,
+        oracles: Vec<Pubkey>,
+        threshold: u8,
+        health_tolerance: u8,
+        trees_tolerance_bps: u16,
+    ) -> Result<()> {
+        let oracle_set = &mut ctx.accounts.oracle_set;
+        oracle_set.version = 0;
+        oracle_set.bump = *ctx.bumps.get("oracle_set").unwrap();
+        set_oracles(oracle_set, oracles, threshold, health_tolerance, trees_tolerance_bps)
+    }
+
+    /// Replace the oracle set, discarding submissions from pending rounds
+    pub fn update_oracle_set(
+        ctx: Context<UpdateOracleSet>,
+        oracles: Vec<Pubkey>,
+        threshold: u8,
+        health_tolerance: u8,
+        trees_tolerance_bps: u16,
+    ) -> Result<()> {
+        set_oracles(&mut ctx.accounts.oracle_set, oracles, threshold, health_tolerance, trees_tolerance_bps)
+    }
+
+    /// Set the collateral oracles must keep staked and how long unstaking takes
+    pub fn configure_oracle_staking(
+        ctx: Context<UpdateOracleSet>,
+        min_stake: u64,
+        unstake_cooldown: i64,
+    ) -> Result<()> {
+        require!(unstake_cooldown >= 0, ErrorCode::InvalidOracleSet);
+
+        let oracle_set = &mut ctx.accounts.oracle_set;
+        oracle_set.min_stake = min_stake;
+        oracle_set.unstake_cooldown = unstake_cooldown;
+
+        emit!(OracleStakingConfigured {
+            min_stake,
+            unstake_cooldown,
+        });
+
+        Ok(())
+    }
+
+    /// Deposit carbon tokens into the oracle's stake vault
+    pub fn stake_oracle(ctx: Context<StakeOracle>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.oracle_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.oracle.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let oracle_stake = &mut ctx.accounts.oracle_stake;
+        if oracle_stake.oracle == Pubkey::default() {
+            oracle_stake.oracle = ctx.accounts.oracle.key();
+            oracle_stake.vault_bump = *ctx.bumps.get("stake_vault").unwrap();
+            oracle_stake.bump = *ctx.bumps.get("oracle_stake").unwrap();
+        }
+        oracle_stake.amount = oracle_stake.amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(OracleStaked {
+            oracle: oracle_stake.oracle,
+            amount,
+            total_staked: oracle_stake.amount,
+        });
+
+        Ok(())
+    }
+
+    /// Start the cooldown for withdrawing part of an oracle's stake
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        let oracle_stake = &mut ctx.accounts.oracle_stake;
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(
+            amount <= oracle_stake.amount - oracle_stake.pending_unstake,
+            ErrorCode::InvalidStakeAmount
+        );
+
+        let current_time = Clock::get()?.unix_timestamp;
+        oracle_stake.pending_unstake += amount;
+        oracle_stake.unstake_requested_at = current_time;
+
+        emit!(OracleUnstakeRequested {
+            oracle: oracle_stake.oracle,
+            amount,
+            pending_unstake: oracle_stake.pending_unstake,
+            withdrawable_at: withdrawable_at(oracle_stake, &ctx.accounts.oracle_set),
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw the pending unstake once the cooldown since the request and the oracle's last report has elapsed
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        let oracle_stake = &ctx.accounts.oracle_stake;
+        let amount = oracle_stake.pending_unstake;
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(
+            Clock::get()?.unix_timestamp >= withdrawable_at(oracle_stake, &ctx.accounts.oracle_set),
+            ErrorCode::UnstakeCooldownActive
+        );
+
+        let oracle_key = oracle_stake.oracle;
+        let stake_seeds = &[
+            b"oracle_stake".as_ref(),
+            oracle_key.as_ref(),
+            &[oracle_stake.bump],
+        ];
+        let signer_seeds = &[&stake_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.oracle_token_account.to_account_info(),
+            authority: ctx.accounts.oracle_stake.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        let oracle_stake = &mut ctx.accounts.oracle_stake;
+        oracle_stake.amount -= amount;
+        oracle_stake.pending_unstake = 0;
+
+        emit!(OracleStakeWithdrawn {
+            oracle: oracle_key,
+            amount,
+            remaining_stake: oracle_stake.amount,
+        });
+
+        Ok(())
+    }
+
+    /// Slash an oracle whose report was proven wrong; the stake joins the platform fees for its mint
+    pub fn slash_oracle(ctx: Context<SlashOracle>, amount: u64, project_id: u64) -> Result<()> {
+        slash_stake(
+            &mut ctx.accounts.oracle_stake,
+            &ctx.accounts.stake_vault,
+            &ctx.accounts.fee_vault,
+            &ctx.accounts.token_program,
+            amount,
+            project_id,
+        )
+    }
+
+    /// Anomaly detection and automated response, applied once a quorum of oracles agrees on the anomaly type
+    pub fn report_anomaly(
+        ctx: Context<ReportAnomaly>,
+        project_id: u64,
+        anomaly_type: AnomalyType,
+        severity: u8,
+        ai_confidence: u8,
+        satellite_evidence_hash: String,
+    ) -> Result<()> {
+        require!(ctx.accounts.oracle_set.oracles.contains(&ctx.accounts.ai_oracle.key()), ErrorCode::UnauthorizedAIOracle);
+        record_oracle_report(&mut ctx.accounts.oracle_stake, &ctx.accounts.oracle_set)?;
+        require!(ai_confidence >= 90, ErrorCode::InsufficientAIConfidence); // High confidence required for anomalies
+        require!(severity <= 100, ErrorCode::InvalidSeverity);
+        require!(satellite_evidence_hash.len() == SATELLITE_HASH_LEN, ErrorCode::InvalidSatelliteHash);
+
+        let project = &mut ctx.accounts.project;
+        require!(project.id == project_id, ErrorCode::ProjectMismatch);
+
+        let oracle_set = &ctx.accounts.oracle_set;
+        let oracle_round = &mut ctx.accounts.oracle_round;
+        if oracle_round.project == Pubkey::default() {
+            oracle_round.project = project.key();
+            oracle_round.bump = *ctx.bumps.get("oracle_round").unwrap();
+        }
+
+        if oracle_round.anomaly_set_version != oracle_set.version {
+            oracle_round.anomaly_submissions.clear();
+            oracle_round.anomaly_set_version = oracle_set.version;
+        }
+
+        let oracle = ctx.accounts.ai_oracle.key();
+        require!(
+            !oracle_round.anomaly_submissions.iter().any(|submission| submission.oracle == oracle),
+            ErrorCode::DuplicateOracleSubmission
+        );
+        oracle_round.anomaly_submissions.push(AnomalySubmission {
+            oracle,
+            report: AnomalyReport {
+                anomaly_type,
+                severity,
+                ai_confidence,
+                satellite_evidence_hash,
+            },
+        });
+
+        emit!(OracleReportSubmitted {
+            project_id,
+            oracle,
+            kind: OracleReportKind::Anomaly,
+            round: oracle_round.anomaly_round,
+            submissions: oracle_round.anomaly_submissions.len() as u8,
+        });
+
+        if oracle_round.anomaly_submissions.len() < oracle_set.threshold as usize {
+            return Ok(());
+        }
+
+        match aggregate_anomaly(&oracle_round.anomaly_submissions, oracle_set.threshold) {
+            Some(report) => {
+                // The anomaly account only exists once quorum is reached, so the index stays free
+                // for community-confirmed anomalies until then
+                let anomaly_bump = *ctx.bumps.get("anomaly").unwrap();
+                let anomaly_info = ctx.accounts.anomaly.to_account_info();
+                let project_key = project.key();
+                let anomaly_index = project.anomaly_count.to_le_bytes();
+                let anomaly_seeds = &[b"anomaly".as_ref(), project_key.as_ref(), anomaly_index.as_ref(), &[anomaly_bump]];
+                let signer_seeds = &[&anomaly_seeds[..]];
+                let system_program_info = ctx.accounts.system_program.to_account_info();
+                let rent = Rent::get()?.minimum_balance(Anomaly::SPACE);
+                if anomaly_info.lamports() == 0 {
+                    let create_accounts = system_program::CreateAccount {
+                        from: ctx.accounts.ai_oracle.to_account_info(),
+                        to: anomaly_info.clone(),
+                    };
+                    let create_ctx = CpiContext::new_with_signer(system_program_info, create_accounts, signer_seeds);
+                    system_program::create_account(create_ctx, rent, Anomaly::SPACE as u64, ctx.program_id)?;
+                } else {
+                    // Someone pre-funded the address, which would make create_account fail
+                    let shortfall = rent.saturating_sub(anomaly_info.lamports());
+                    if shortfall > 0 {
+                        let transfer_accounts = system_program::Transfer {
+                            from: ctx.accounts.ai_oracle.to_account_info(),
+                            to: anomaly_info.clone(),
+                        };
+                        let transfer_ctx = CpiContext::new(system_program_info.clone(), transfer_accounts);
+                        system_program::transfer(transfer_ctx, shortfall)?;
+                    }
+                    let allocate_accounts = system_program::Allocate { account_to_allocate: anomaly_info.clone() };
+                    let allocate_ctx = CpiContext::new_with_signer(system_program_info.clone(), allocate_accounts, signer_seeds);
+                    system_program::allocate(allocate_ctx, Anomaly::SPACE as u64)?;
+                    let assign_accounts = system_program::Assign { account_to_assign: anomaly_info.clone() };
+                    let assign_ctx = CpiContext::new_with_signer(system_program_info, assign_accounts, signer_seeds);
+                    system_program::assign(assign_ctx, ctx.program_id)?;
+                }
+
+                let mut anomaly = Anomaly::try_deserialize_unchecked(&mut &anomaly_info.try_borrow_data()?[..])?;
+                anomaly.bump = anomaly_bump;
+                record_anomaly(project, &mut anomaly, anomaly_info.key(), report, oracle, Clock::get()?.unix_timestamp);
+                anomaly.try_serialize(&mut &mut anomaly_info.try_borrow_mut_data()?[..])?;
+            }
+            None if oracle_round.anomaly_submissions.len() >= oracle_set.oracles.len() => {
+                emit!(OracleRoundFailed {
+                    project_id,
+                    kind: OracleReportKind::Anomaly,
+                    round: oracle_round.anomaly_round,
+                });
+            }
+            None => return Ok(()),
+        }
+
+        oracle_round.anomaly_submissions.clear();
+        oracle_round.anomaly_round += 1;
+
+        Ok(())
+    }
+
+    /// Mark an open anomaly as under investigation
+    pub fn acknowledge_anomaly(ctx: Context<ReviewAnomaly>) -> Result<()> {
+        let anomaly = &mut ctx.accounts.anomaly;
+        require!(anomaly.status == AnomalyStatus::Open, ErrorCode::AnomalyNotOpen);
+        anomaly.status = AnomalyStatus::Investigating;
+
+        emit!(AnomalyStatusChanged {
+            project_id: anomaly.project_id,
+            anomaly: anomaly.key(),
+            status: anomaly.status,
+            reviewer: ctx.accounts.reviewer.key(),
+            restored_status: None,
+        });
+
+        Ok(())
+    }
+
+    /// Close an anomaly as resolved; a suspended project returns to its prior status once no suspending anomaly is open
+    pub fn resolve_anomaly(ctx: Context<ReviewAnomaly>) -> Result<()> {
+        close_anomaly(ctx, AnomalyStatus::Resolved)
+    }
+
+    /// Close an anomaly as a false positive; a suspended project returns to its prior status once no suspending anomaly is open
+    pub fn dismiss_anomaly(ctx: Context<ReviewAnomaly>) -> Result<()> {
+        require!(!ctx.accounts.anomaly.reversal_applied, ErrorCode::AnomalyReversalApplied);
+        close_anomaly(ctx, AnomalyStatus::Dismissed)
+    }
+
+    /// Report an anomaly as a community member, posting a lamport bond with the evidence
+    pub fn submit_community_report(
+        ctx: Context<SubmitCommunityReport>,
+        anomaly_type: AnomalyType,
+        evidence_hash: String,
+    ) -> Result<()> {
+        require!(!ctx.accounts.platform_state.emergency_pause, ErrorCode::PlatformPaused);
+        require!(evidence_hash.len() == SATELLITE_HASH_LEN, ErrorCode::InvalidSatelliteHash);
+
+        // The bond sits on the report account until it is reviewed
+        let bond_accounts = system_program::Transfer {
+            from: ctx.accounts.reporter.to_account_info(),
+            to: ctx.accounts.report.to_account_info(),
+        };
+        let bond_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), bond_accounts);
+        system_program::transfer(bond_ctx, COMMUNITY_REPORT_BOND)?;
+
+        let project = &mut ctx.accounts.project;
+        let report = &mut ctx.accounts.report;
+        report.project = project.key();
+        report.project_id = project.id;
+        report.index = project.community_report_count;
+        report.reporter = ctx.accounts.reporter.key();
+        report.anomaly_type = anomaly_type;
+        report.evidence_hash = evidence_hash;
+        report.bond = COMMUNITY_REPORT_BOND;
+        report.status = CommunityReportStatus::Pending;
+        report.submitted_at = Clock::get()?.unix_timestamp;
+        report.reviewed_at = 0;
+        report.reviewer = Pubkey::default();
+        report.bump = *ctx.bumps.get("report").unwrap();
+        project.community_report_count += 1;
+
+        emit!(CommunityReportSubmitted {
+            project_id: project.id,
+            report: report.key(),
+            reporter: report.reporter,
+            anomaly_type,
+            evidence_hash: report.evidence_hash.clone(),
+            bond: report.bond,
+        });
+
+        Ok(())
+    }
+
+    /// Confirm a community report: the bond comes back with a bounty and the report becomes an anomaly
+    pub fn confirm_community_report(ctx: Context<ConfirmCommunityReport>, severity: u8) -> Result<()> {
+        require_community_reviewer(&ctx.accounts.reviewer, &ctx.accounts.oracle_set, &ctx.accounts.role)?;
+        require!(ctx.accounts.reviewer.key() != ctx.accounts.report.reporter, ErrorCode::SelfReview);
+        require!(severity <= 100, ErrorCode::InvalidSeverity);
+        require!(ctx.accounts.report.status == CommunityReportStatus::Pending, ErrorCode::CommunityReportNotPending);
+
+        // Return the bond from the report account
+        let bond = ctx.accounts.report.bond;
+        **ctx.accounts.report.to_account_info().try_borrow_mut_lamports()? -= bond;
+        **ctx.accounts.reporter.to_account_info().try_borrow_mut_lamports()? += bond;
+
+        // Pay what the treasury can afford without dropping below rent exemption; it holds rejected
+        // bonds plus whatever the platform deposits through fund_community_treasury
+        let treasury_floor = Rent::get()?.minimum_balance(0);
+        let bounty = COMMUNITY_REPORT_BOUNTY.min(ctx.accounts.community_treasury.lamports().saturating_sub(treasury_floor));
+        if bounty > 0 {
+            let treasury_seeds = &[
+                b"community_treasury".as_ref(),
+                &[*ctx.bumps.get("community_treasury").unwrap()],
+            ];
+            let signer_seeds = &[&treasury_seeds[..]];
+
+            let bounty_accounts = system_program::Transfer {
+                from: ctx.accounts.community_treasury.to_account_info(),
+                to: ctx.accounts.reporter.to_account_info(),
+            };
+            let bounty_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                bounty_accounts,
+                signer_seeds,
+            );
+            system_program::transfer(bounty_ctx, bounty)?;
+        }
+
+        let report = &mut ctx.accounts.report;
+        report.status = CommunityReportStatus::Confirmed;
+        report.reviewed_at = Clock::get()?.unix_timestamp;
+        report.reviewer = ctx.accounts.reviewer.key();
+
+        emit!(CommunityReportReviewed {
+            project_id: report.project_id,
+            report: report.key(),
+            reporter: report.reporter,
+            reviewer: report.reviewer,
+            confirmed: true,
+            bond,
+            bounty,
+        });
+
+        // Confirmed reports go through the same response as oracle-detected anomalies
+        let anomaly_report = AnomalyReport {
+            anomaly_type: report.anomaly_type,
+            severity,
+            ai_confidence: 0, // Human-reviewed rather than AI-scored
+            satellite_evidence_hash: report.evidence_hash.clone(),
+        };
+        let reporter = report.reporter;
+        let reviewed_at = report.reviewed_at;
+        let anomaly = &mut ctx.accounts.anomaly;
+        anomaly.bump = *ctx.bumps.get("anomaly").unwrap();
+        let anomaly_key = anomaly.key();
+        record_anomaly(&mut ctx.accounts.project, anomaly, anomaly_key, anomaly_report, reporter, reviewed_at);
+
+        Ok(())
+    }
+
+    /// Reject a community report, slashing its bond into the community treasury
+    pub fn reject_community_report(ctx: Context<RejectCommunityReport>) -> Result<()> {
+        require_community_reviewer(&ctx.accounts.reviewer, &ctx.accounts.oracle_set, &ctx.accounts.role)?;
+        require!(ctx.accounts.report.status == CommunityReportStatus::Pending, ErrorCode::CommunityReportNotPending);
+
+        let bond = ctx.accounts.report.bond;
+        **ctx.accounts.report.to_account_info().try_borrow_mut_lamports()? -= bond;
+        **ctx.accounts.community_treasury.to_account_info().try_borrow_mut_lamports()? += bond;
+
+        let report = &mut ctx.accounts.report;
+        report.status = CommunityReportStatus::Rejected;
+        report.reviewed_at = Clock::get()?.unix_timestamp;
+        report.reviewer = ctx.accounts.reviewer.key();
+
+        emit!(CommunityReportReviewed {
+            project_id: report.project_id,
+            report: report.key(),
+            reporter: report.reporter,
+            reviewer: report.reviewer,
+            confirmed: false,
+            bond,
+            bounty: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Deposit lamports into the community treasury that pays bounties for confirmed reports
+    pub fn fund_community_treasury(ctx: Context<FundCommunityTreasury>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidTransferAmount);
+
+        let transfer_accounts = system_program::Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.community_treasury.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts);
+        system_program::transfer(transfer_ctx, amount)?;
+
+        emit!(CommunityTreasuryFunded {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            balance: ctx.accounts.community_treasury.lamports(),
+        });
+
+        Ok(())
+    }
+
+    /// Appeal the anomaly that suspended a project, pointing reviewers at counter-evidence
+    pub fn file_appeal(ctx: Context<FileAppeal>, counter_evidence_hash: String) -> Result<()> {
+        require!(counter_evidence_hash.len() == SATELLITE_HASH_LEN, ErrorCode::InvalidSatelliteHash);
+        require!(ctx.accounts.appellant.key() == ctx.accounts.project.creator, ErrorCode::UnauthorizedProjectCreator);
+        require!(ctx.accounts.project.status == ProjectStatus::Suspended, ErrorCode::ProjectNotSuspended);
+
+        let anomaly = &ctx.accounts.anomaly;
+        require!(anomaly.severity >= 80, ErrorCode::AnomalyNotSuspending);
+        require!(
+            matches!(anomaly.status, AnomalyStatus::Open | AnomalyStatus::Investigating),
+            ErrorCode::AnomalyClosed
+        );
+
+        let appeal = &mut ctx.accounts.appeal;
+        appeal.project = ctx.accounts.project.key();
+        appeal.anomaly = anomaly.key();
+        appeal.appellant = ctx.accounts.appellant.key();
+        appeal.counter_evidence_hash = counter_evidence_hash;
+        appeal.status = AppealStatus::Pending;
+        appeal.filed_at = Clock::get()?.unix_timestamp;
+        appeal.ruled_at = 0;
+        appeal.reviewer = Pubkey::default();
+        appeal.bump = *ctx.bumps.get("appeal").unwrap();
+
+        emit!(AppealFiled {
+            project_id: ctx.accounts.project.id,
+            anomaly: appeal.anomaly,
+            appeal: appeal.key(),
+            appellant: appeal.appellant,
+            counter_evidence_hash: appeal.counter_evidence_hash.clone(),
+        });
+
+        Ok(())
+    }
+
+    /// Rule on a pending appeal; upholding it dismisses the anomaly and reinstates the project if nothing else holds it suspended
+    pub fn rule_on_appeal(ctx: Context<RuleOnAppeal>, upheld: bool) -> Result<()> {
+        let appeal = &mut ctx.accounts.appeal;
+        require!(appeal.status == AppealStatus::Pending, ErrorCode::AppealNotPending);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        appeal.status = if upheld { AppealStatus::Upheld } else { AppealStatus::Denied };
+        appeal.ruled_at = current_time;
+        appeal.reviewer = ctx.accounts.reviewer.key();
+
+        let project = &mut ctx.accounts.project;
+        let anomaly = &mut ctx.accounts.anomaly;
+        let mut restored_status = None;
+        if upheld {
+            require!(
+                matches!(anomaly.status, AnomalyStatus::Open | AnomalyStatus::Investigating),
+                ErrorCode::AnomalyClosed
+            );
+            require!(!anomaly.reversal_applied, ErrorCode::AnomalyReversalApplied);
+            anomaly.status = AnomalyStatus::Dismissed;
+            anomaly.closed_at = current_time;
+            anomaly.closed_by = ctx.accounts.reviewer.key();
+
+            if anomaly.severity >= 80 {
+                restored_status = release_suspension(project);
+            }
+            // Undo only this anomaly's own health cut, leaving later anomalies' cuts in place
+            project.ai_health_score = project.ai_health_score
+                .saturating_add(anomaly.health_penalty)
+                .min(100);
+        }
+
+        emit!(AppealRuled {
+            project_id: project.id,
+            anomaly: anomaly.key(),
+            appeal: appeal.key(),
+            reviewer: appeal.reviewer,
+            upheld,
+            restored_status,
+            ai_health_score: project.ai_health_score,
+        });
+
+        Ok(())
+    }
+}
+
+fn close_anomaly(ctx: Context<ReviewAnomaly>, status: AnomalyStatus) -> Result<()> {
+    let anomaly = &mut ctx.accounts.anomaly;
+    require!(
+        matches!(anomaly.status, AnomalyStatus::Open | AnomalyStatus::Investigating),
+        ErrorCode::AnomalyClosed
+    );
+    anomaly.status = status;
+    anomaly.closed_at = Clock::get()?.unix_timestamp;
+    anomaly.closed_by = ctx.accounts.reviewer.key();
+
+    let project = &mut ctx.accounts.project;
+    let restored_status = if anomaly.severity >= 80 { release_suspension(project) } else { None };
+
+    emit!(AnomalyStatusChanged {
+        project_id: anomaly.project_id,
+        anomaly: anomaly.key(),
+        status,
+        reviewer: ctx.accounts.reviewer.key(),
+        restored_status,
+    });
+
+    Ok(())
+}
+
+/// Close out one suspending anomaly; the project returns to its prior status once none remain open
+fn release_suspension(project: &mut Project) -> Option<ProjectStatus> {
+    project.suspending_anomalies = project.suspending_anomalies.saturating_sub(1);
+    if project.suspending_anomalies > 0 || project.status != ProjectStatus::Suspended {
+        return None;
+    }
+    let previous = project.status_before_suspension.take()?;
+    project.status = previous;
+    Some(previous)
+}
+
+/// Add a deposit to an investment, weighting it into both credit accumulators from this point on
+fn record_deposit(project: &mut Project, investment: &mut Investment, amount: u64, multiplier: u16) -> Result<()> {
+    let total_amount = investment.amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Bonus is the amount-weighted average of the multiplier earned by each deposit
+    let weighted_bonus = investment.amount as u128 * investment.bonus_multiplier as u128
+        + amount as u128 * multiplier as u128;
+    investment.bonus_multiplier = (weighted_bonus / total_amount as u128) as u16;
+    investment.amount = total_amount;
+    // New deposits only vest credits verified from now on
+    investment.credit_debt += amount as u128 * project.credits_per_share;
+    // Each deposit adds its own exact weight, so a top-up never shrinks an earlier one's share
+    let added_weight = amount as u128 * (multiplier as u128).saturating_sub(100);
+    investment.bonus_weight = investment.bonus_weight
+        .checked_add(added_weight)
+        .ok_or(ErrorCode::MathOverflow)?;
+    investment.bonus_debt = investment.bonus_debt
+        .checked_add(added_weight * project.bonus_per_weight)
+        .ok_or(ErrorCode::MathOverflow)?;
+    project.bonus_weight = project.bonus_weight
+        .checked_add(added_weight)
+        .ok_or(ErrorCode::MathOverflow)?;
+    project.current_funding += amount;
+
+    Ok(())
+}
+
+/// Settle an investment's vested credits into a claim, returning (total, bonus) credits to mint
+fn settle_claim(project: &mut Project, investment: &mut Investment) -> (u64, u64) {
+    // Base credits vested since the last claim, from the project's credits-per-share accumulator
+    let accrued = investment.amount as u128 * project.credits_per_share;
+    let base_credits = (accrued.saturating_sub(investment.credit_debt) / CREDITS_PRECISION) as u64;
+
+    // Tier bonuses come from the bonus pool, shared pro-rata by bonus weight through its own accumulator
+    let bonus_accrued = investment.bonus_weight * project.bonus_per_weight;
+    let bonus_credits = (bonus_accrued.saturating_sub(investment.bonus_debt) / CREDITS_PRECISION) as u64;
+
+    // Never mint past what the oracles have verified; base credits are paid out first
+    let total_credits = (base_credits + bonus_credits)
+        .min(project.co2_accrued.saturating_sub(project.carbon_credits_minted));
+    let base_minted = base_credits.min(total_credits);
+    let bonus_minted = total_credits - base_minted;
+
+    // Anything cut off by the cap stays owed for a later claim
+    investment.credit_debt += base_minted as u128 * CREDITS_PRECISION;
+    investment.bonus_debt += bonus_minted as u128 * CREDITS_PRECISION;
+    project.carbon_credits_minted += total_credits;
+    project.bonus_credits_issued += bonus_minted;
+
+    (total_credits, bonus_minted)
+}
+
+/// Persist an anomaly that reached quorum and apply the automated response to its project
+fn record_anomaly(
+    project: &mut Account<Project>,
+    anomaly: &mut Anomaly,
+    anomaly_key: Pubkey,
+    report: AnomalyReport,
+    reporter: Pubkey,
+    current_time: i64,
+) {
+    anomaly.project = project.key();
+    anomaly.project_id = project.id;
+    anomaly.index = project.anomaly_count;
+    anomaly.anomaly_type = report.anomaly_type;
+    anomaly.severity = report.severity;
+    anomaly.ai_confidence = report.ai_confidence;
+    anomaly.evidence_hash = report.satellite_evidence_hash.clone();
+    anomaly.reporter = reporter;
+    anomaly.status = AnomalyStatus::Open;
+    anomaly.reported_at = current_time;
+    anomaly.closed_at = 0;
+    anomaly.closed_by = Pubkey::default();
+    anomaly.health_penalty = 0;
+    anomaly.reversal_applied = false;
+    project.anomaly_count += 1;
+
+    // Automated response based on severity
+    if report.severity >= 80 {
+        if project.status != ProjectStatus::Suspended {
+            project.status_before_suspension = Some(project.status);
+        }
+        project.suspending_anomalies += 1;
+        project.status = ProjectStatus::Suspended; // Auto-suspend high-severity anomalies
+    } else if report.severity >= 50 {
+        anomaly.health_penalty = (report.severity / 2).min(project.ai_health_score);
+        project.ai_health_score -= anomaly.health_penalty; // Reduce health score
+    }
+
+    emit!(AnomalyDetected {
+        project_id: project.id,
+        anomaly: anomaly_key,
+        index: anomaly.index,
+        anomaly_type: report.anomaly_type,
+        severity: report.severity,
+        ai_confidence: report.ai_confidence,
+        automated_action: if report.severity >= 80 { "suspended".to_string() } else { "health_reduced".to_string() },
+        satellite_evidence: report.satellite_evidence_hash,
+    });
+}
+
+/// Community reports are reviewed by an oracle in the active set or a project approver
+fn require_community_reviewer(
+    reviewer: &Signer,
+    oracle_set: &OracleSet,
+    role: &Option<Account<RoleAssignment>>,
+) -> Result<()> {
+    require!(
+        oracle_set.oracles.contains(&reviewer.key()) || role.is_some(),
+        ErrorCode::UnauthorizedCommunityReviewer
+    );
+    Ok(())
+}
+
+/// Type of a still-standing fire or deforestation anomaly that hasn't been charged against credits yet
+fn reversal_anomaly_type(anomaly: &Anomaly) -> Result<AnomalyType> {
+    require!(anomaly.status != AnomalyStatus::Dismissed, ErrorCode::NoReversalAnomaly);
+    require!(!anomaly.reversal_applied, ErrorCode::AnomalyReversalApplied);
+    match anomaly.anomaly_type {
+        AnomalyType::Fire | AnomalyType::Deforestation => Ok(anomaly.anomaly_type),
+        _ => err!(ErrorCode::NoReversalAnomaly),
+    }
+}
+
+/// Debit each investment's pro-rata share of the latest reversal from its unclaimed credits
+fn debit_investor_reversals<'info>(project: &mut Project, investments: &'info [AccountInfo<'info>]) -> Result<()> {
+    for info in investments.iter() {
+        require!(info.is_writable, ErrorCode::InvalidReversalAccount);
+        let mut investment = Account::<Investment>::try_from(info)?;
+        require!(investment.project_id == project.id, ErrorCode::ProjectMismatch);
+        require!(investment.reversals_applied < project.reversal_count, ErrorCode::ReversalAlreadyApplied);
+
+        debit_investment_reversal(project, &mut investment, info.key());
+        investment.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/// Debit one investment's pro-rata share of the latest reversal from its unclaimed credits
+fn debit_investment_reversal(project: &mut Project, investment: &mut Investment, investment_key: Pubkey) {
+    let accrued = investment.amount as u128 * project.credits_per_share;
+    let unclaimed = (accrued.saturating_sub(investment.credit_debt) / CREDITS_PRECISION) as u64;
+    let share = if project.current_funding == 0 {
+        0
+    } else {
+        (project.reversal_investor_loss as u128 * investment.amount as u128
+            / project.current_funding as u128) as u64
+    };
+    let debit = share
+        .min(unclaimed)
+        .min(project.reversal_investor_loss.saturating_sub(project.reversal_debited));
+
+    investment.credit_debt += debit as u128 * CREDITS_PRECISION;
+    investment.credits_reversed += debit;
+    investment.reversals_applied = project.reversal_count;
+
+    project.reversal_debited += debit;
+    project.reversal_investors_applied += 1;
+    project.total_reversed += debit;
+    project.co2_accrued = project.co2_accrued.saturating_sub(debit);
+
+    emit!(CreditsReversed {
+        project_id: project.id,
+        reversal: project.reversal_count,
+        investment: investment_key,
+        investor: investment.investor,
+        amount: debit,
+        unclaimed_remaining: unclaimed - debit,
+    });
+}
+
+/// Transfer `amount` out of the fee vault, split across the fee split's recipients; rounding dust goes to the first
+fn distribute_fees<'info>(
+    platform_state: &Account<'info, PlatformState>,
+    fee_vault: &Account<'info, TokenAccount>,
+    fee_split: &FeeSplit,
+    token_program: &AccountInfo<'info>,
+    recipients: &'info [AccountInfo<'info>],
+    amount: u64,
+    withdrawn_by: Pubkey,
+) -> Result<()> {
+    require!(amount > 0 && amount <= fee_vault.amount, ErrorCode::InsufficientFees);
+    require!(recipients.len() == fee_split.recipients.len(), ErrorCode::InvalidFeeRecipients);
+
+    let platform_seeds = &[
+        b"platform_state".as_ref(),
+        &[platform_state.bump],
+    ];
+    let signer_seeds = &[&platform_seeds[..]];
+
+    let mut payouts = Vec::with_capacity(recipients.len());
+    for (info, recipient) in recipients.iter().zip(fee_split.recipients.iter()) {
+        let token_account = Account::<TokenAccount>::try_from(info)?;
+        require!(
+            token_account.owner == recipient.wallet && token_account.mint == fee_vault.mint,
+            ErrorCode::InvalidFeeRecipients
+        );
+        payouts.push((amount as u128 * recipient.bps as u128 / 10000) as u64);
+    }
+    payouts[0] += amount - payouts.iter().sum::<u64>();
+
+    for (info, payout) in recipients.iter().zip(payouts.iter()) {
+        if *payout == 0 {
+            continue;
+        }
+        let cpi_accounts = Transfer {
+            from: fee_vault.to_account_info(),
+            to: info.clone(),
+            authority: platform_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, *payout)?;
+    }
+
+    emit!(FeesWithdrawn {
+        mint: fee_vault.mint,
+        amount,
+        recipients: fee_split.recipients.iter().map(|recipient| recipient.wallet).collect(),
+        amounts: payouts,
+        withdrawn_by,
+    });
+
+    Ok(())
+}
+
+/// Calendar year (UTC) of a unix timestamp, used as a credit batch's vintage
+fn vintage_year(timestamp: i64) -> u16 {
+    // Days-to-civil conversion from Howard Hinnant's date algorithms
+    let z = timestamp.div_euclid(86_400) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    year as u16
+}
+
+/// Highest tier a cumulative investment qualifies for, with its bonus multiplier
+fn tier_for_amount(amount: u64) -> Option<(InvestmentTier, u16)> {
+    match amount {
+        100_000_000.. => Some((InvestmentTier::Diamond, 200)), // 100 SOL, 2x
+        50_000_000.. => Some((InvestmentTier::Platinum, 150)), // 50 SOL, 1.5x
+        10_000_000.. => Some((InvestmentTier::Gold, 125)),     // 10 SOL, 1.25x
+        5_000_000.. => Some((InvestmentTier::Silver, 110)),    // 5 SOL, 1.1x
+        1_000_000.. => Some((InvestmentTier::Bronze, 100)),    // 1 SOL, 1x
+        _ => None,
+    }
+}
+
+fn queue_config_update(
+    platform_state: &mut PlatformState,
+    investment_fee_bps: u16,
+    min_ai_confidence: u8,
+    ai_update_interval: i64,
+    buffer_bps: u16,
+) -> Result<()> {
+    require!(investment_fee_bps <= 10000, ErrorCode::InvalidConfigValue);
+    require!(min_ai_confidence <= 100, ErrorCode::InvalidConfigValue);
+    require!(ai_update_interval >= 0, ErrorCode::InvalidConfigValue);
+    require!(buffer_bps <= 5000, ErrorCode::InvalidConfigValue); // Doubled for the riskiest projects
+
+    let executable_at = Clock::get()?.unix_timestamp + CONFIG_TIMELOCK;
+    platform_state.pending_config = Some(PendingConfigUpdate {
+        investment_fee_bps,
+        min_ai_confidence,
+        ai_update_interval,
+        buffer_bps,
+        executable_at,
+    });
+
+    emit!(ConfigUpdateQueued {
+        old_investment_fee_bps: platform_state.investment_fee_bps,
+        new_investment_fee_bps: investment_fee_bps,
+        old_min_ai_confidence: platform_state.min_ai_confidence,
+        new_min_ai_confidence: min_ai_confidence,
+        old_ai_update_interval: platform_state.ai_update_interval,
+        new_ai_update_interval: ai_update_interval,
+        old_buffer_bps: platform_state.buffer_bps,
+        new_buffer_bps: buffer_bps,
+        executable_at,
+    });
+
+    Ok(())
+}
+
+fn mark_project_failed(project: &mut Project, escrow_balance: u64) -> Result<()> {
+    project.status = ProjectStatus::Failed;
+    project.refund_pool = escrow_balance;
+
+    emit!(ProjectFailed {
+        project_id: project.id,
+        refund_pool: project.refund_pool,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn set_oracles(
+    oracle_set: &mut OracleSet,
+    oracles: Vec<Pubkey>,
+    threshold: u8,
+    health_tolerance: u8,
+    trees_tolerance_bps: u16,
+) -> Result<()> {
+    require!(!oracles.is_empty() && oracles.len() <= MAX_ORACLES, ErrorCode::InvalidOracleSet);
+    require!(threshold > 0 && threshold as usize <= oracles.len(), ErrorCode::InvalidOracleSet);
+    require!(health_tolerance <= 100 && trees_tolerance_bps <= 10000, ErrorCode::InvalidOracleSet);
+    for (index, oracle) in oracles.iter().enumerate() {
+        require!(!oracles[..index].contains(oracle), ErrorCode::InvalidOracleSet);
+    }
+
+    oracle_set.oracles = oracles;
+    oracle_set.threshold = threshold;
+    oracle_set.health_tolerance = health_tolerance;
+    oracle_set.trees_tolerance_bps = trees_tolerance_bps;
+    oracle_set.version += 1;
+
+    emit!(OracleSetUpdated {
+        oracles: oracle_set.oracles.clone(),
+        threshold,
+        health_tolerance,
+        trees_tolerance_bps,
+        version: oracle_set.version,
+    });
+
+    Ok(())
+}
+
+/// Validate an oracle's progress report and add it to the project's current round, applying the median once a quorum agrees
+#[allow(clippy::too_many_arguments)]
+fn submit_progress_report<'info>(
+    project: &mut Account<'info, Project>,
+    platform_state: &Account<'info, PlatformState>,
+    oracle_set: &OracleSet,
+    oracle_round: &mut Account<'info, OracleRound>,
+    oracle_stake: &mut OracleStake,
+    oracle: Pubkey,
+    round_bump: u8,
+    report: ProgressReport,
+) -> Result<()> {
+    require!(!platform_state.emergency_pause, ErrorCode::PlatformPaused);
+    require!(oracle_set.oracles.contains(&oracle), ErrorCode::UnauthorizedAIOracle);
+    record_oracle_report(oracle_stake, oracle_set)?;
+    require!(report.ai_confidence >= platform_state.min_ai_confidence, ErrorCode::InsufficientAIConfidence);
+    require!(report.satellite_verification_hash.len() == SATELLITE_HASH_LEN, ErrorCode::InvalidSatelliteHash);
+    require!(report.trees_planted <= project.target_trees, ErrorCode::ExceedsTargetTrees);
+    require!(project.status == ProjectStatus::Active || project.status == ProjectStatus::Funded, ErrorCode::ProjectNotActive);
+
+    // Verify AI update interval
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time - project.last_ai_update >= platform_state.ai_update_interval,
+        ErrorCode::TooFrequentAIUpdate
+    );
+
+    if oracle_round.project == Pubkey::default() {
+        oracle_round.project = project.key();
+        oracle_round.bump = round_bump;
+    }
+    require!(oracle_round.pending_progress.is_none(), ErrorCode::ProgressReportPending);
+
+    // Submissions gathered under a previous oracle set no longer count
+    if oracle_round.progress_set_version != oracle_set.version {
+        oracle_round.progress_submissions.clear();
+        oracle_round.progress_set_version = oracle_set.version;
+    }
+
+    require!(
+        !oracle_round.progress_submissions.iter().any(|submission| submission.oracle == oracle),
+        ErrorCode::DuplicateOracleSubmission
+    );
+    oracle_round.progress_submissions.push(ProgressSubmission { oracle, report });
+
+    emit!(OracleReportSubmitted {
+        project_id: project.id,
+        oracle,
+        kind: OracleReportKind::Progress,
+        round: oracle_round.progress_round,
+        submissions: oracle_round.progress_submissions.len() as u8,
+    });
+
+    if oracle_round.progress_submissions.len() < oracle_set.threshold as usize {
+        return Ok(());
+    }
+
+    match aggregate_progress(&oracle_round.progress_submissions, oracle_set) {
+        Some((report, submitters)) => {
+            // Agreed reports wait out the challenge period before touching the project
+            emit!(ProgressReportPending {
+                project_id: project.id,
+                round: oracle_round.progress_round,
+                trees_planted: report.trees_planted,
+                co2_sequestered: report.co2_sequestered,
+                challenge_ends_at: current_time.saturating_add(oracle_set.challenge_period),
+            });
+            oracle_round.pending_progress = Some(PendingProgress {
+                round: oracle_round.progress_round,
+                report,
+                submitters,
+                submitted_at: current_time,
+                disputed: false,
+                dispute_cleared: false,
+            });
+        }
+        None if oracle_round.progress_submissions.len() >= oracle_set.oracles.len() => {
+            // Every oracle has reported without agreement, start over
+            emit!(OracleRoundFailed {
+                project_id: project.id,
+                kind: OracleReportKind::Progress,
+                round: oracle_round.progress_round,
+            });
+        }
+        None => return Ok(()),
+    }
+
+    oracle_round.progress_submissions.clear();
+    oracle_round.progress_round += 1;
+
+    Ok(())
+}
+
+/// Bytes an oracle signs for an attestation. The domain tag, program id and platform carbon mint
+/// bind the signature to this deployment, so it cannot be replayed on another cluster.
+fn attestation_message(program_id: &Pubkey, platform_state: &PlatformState, attestation: &ProgressAttestation) -> Result<Vec<u8>> {
+    let mut message = Vec::with_capacity(ATTESTATION_DOMAIN.len() + 64 + 128);
+    message.extend_from_slice(ATTESTATION_DOMAIN);
+    message.extend_from_slice(program_id.as_ref());
+    message.extend_from_slice(platform_state.carbon_token_mint.as_ref());
+    message.extend_from_slice(&attestation.try_to_vec()?);
+    Ok(message)
+}
+
+/// Check that the instruction before the current one is an Ed25519 precompile call
+/// verifying `message` signed by `signer`, with all data inline in that instruction
+fn verify_ed25519_instruction(instructions_sysvar: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::InvalidAttestationSignature);
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(ed25519_ix.program_id == ed25519_program::ID, ErrorCode::InvalidAttestationSignature);
+    require!(ed25519_ix.accounts.is_empty(), ErrorCode::InvalidAttestationSignature);
+
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidAttestationSignature);
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+
+    // Offsets header follows the signature count and padding byte
+    let signature_ix_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_index = read_u16(14);
+    require!(
+        signature_ix_index == u16::MAX && public_key_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        ErrorCode::InvalidAttestationSignature
+    );
+
+    let signed_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidAttestationSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidAttestationSignature)?;
+    require!(signed_key == signer.as_ref(), ErrorCode::InvalidAttestationSignature);
+    require!(signed_message == message, ErrorCode::InvalidAttestationSignature);
+
+    Ok(())
+}
+
+/// Oracles must keep the minimum stake, net of pending withdrawals, to report
+fn record_oracle_report(oracle_stake: &mut OracleStake, oracle_set: &OracleSet) -> Result<()> {
+    require!(
+        oracle_stake.amount - oracle_stake.pending_unstake >= oracle_set.min_stake,
+        ErrorCode::InsufficientOracleStake
+    );
+    oracle_stake.last_report_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+/// The cooldown restarts on every report so an oracle cannot exit right after submitting bad data
+fn withdrawable_at(oracle_stake: &OracleStake, oracle_set: &OracleSet) -> i64 {
+    oracle_stake
+        .unstake_requested_at
+        .max(oracle_stake.last_report_at)
+        .saturating_add(oracle_set.unstake_cooldown)
+}
+
+fn slash_stake<'info>(
+    oracle_stake: &mut Account<'info, OracleStake>,
+    stake_vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+    project_id: u64,
+) -> Result<()> {
+    require!(amount > 0 && amount <= oracle_stake.amount, ErrorCode::InvalidStakeAmount);
+
+    let oracle_key = oracle_stake.oracle;
+    let stake_seeds = &[
+        b"oracle_stake".as_ref(),
+        oracle_key.as_ref(),
+        &[oracle_stake.bump],
+    ];
+    let signer_seeds = &[&stake_seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: stake_vault.to_account_info(),
+        to: destination.to_account_info(),
+        authority: oracle_stake.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)?;
+
+    oracle_stake.amount -= amount;
+    oracle_stake.pending_unstake = oracle_stake.pending_unstake.min(oracle_stake.amount);
+    oracle_stake.slashed_total = oracle_stake.slashed_total
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(OracleSlashed {
+        oracle: oracle_key,
+        project_id,
+        amount,
+        remaining_stake: oracle_stake.amount,
+    });
+
+    Ok(())
+}
+
+/// Lower median, so the result is always a value some oracle actually reported
+fn median<T: Ord + Copy>(mut values: Vec<T>) -> T {
+    values.sort_unstable();
+    values[(values.len() - 1) / 2]
+}
+
+fn within_bps(value: u64, reference: u64, tolerance_bps: u16) -> bool {
+    value.abs_diff(reference) as u128 * 10000 <= reference as u128 * tolerance_bps as u128
+}
+
+/// Returns the median report together with the oracles whose reports fell within tolerance of it
+fn aggregate_progress(submissions: &[ProgressSubmission], oracle_set: &OracleSet) -> Option<(ProgressReport, Vec<Pubkey>)> {
+    let trees_planted = median(submissions.iter().map(|s| s.report.trees_planted).collect());
+    let ai_health_score = median(submissions.iter().map(|s| s.report.ai_health_score).collect());
+    let ai_confidence = median(submissions.iter().map(|s| s.report.ai_confidence).collect());
+    let co2_sequestered = median(submissions.iter().map(|s| s.report.co2_sequestered).collect());
+
+    let agreeing: Vec<Pubkey> = submissions
+        .iter()
+        .filter(|s| {
+            within_bps(s.report.trees_planted, trees_planted, oracle_set.trees_tolerance_bps)
+                && within_bps(s.report.co2_sequestered, co2_sequestered, oracle_set.trees_tolerance_bps)
+                && s.report.ai_health_score.abs_diff(ai_health_score) <= oracle_set.health_tolerance
+        })
+        .map(|s| s.oracle)
+        .collect();
+    if agreeing.len() < oracle_set.threshold as usize {
+        return None;
+    }
+
+    // Satellite evidence comes from the report that set the median tree count
+    let satellite_verification_hash = submissions
+        .iter()
+        .find(|s| s.report.trees_planted == trees_planted)?
+        .report
+        .satellite_verification_hash
+        .clone();
+
+    let report = ProgressReport {
+        trees_planted,
+        ai_health_score,
+        ai_confidence,
+        co2_sequestered,
+        satellite_verification_hash,
+    };
+    Some((report, agreeing))
+}
+
+fn aggregate_anomaly(submissions: &[AnomalySubmission], threshold: u8) -> Option<AnomalyReport> {
+    let anomaly_type = submissions
+        .iter()
+        .map(|s| s.report.anomaly_type)
+        .find(|candidate| {
+            submissions.iter().filter(|s| s.report.anomaly_type == *candidate).count() >= threshold as usize
+        })?;
+    let matching: Vec<&AnomalySubmission> = submissions
+        .iter()
+        .filter(|s| s.report.anomaly_type == anomaly_type)
+        .collect();
+
+    let severity = median(matching.iter().map(|s| s.report.severity).collect());
+    let ai_confidence = median(matching.iter().map(|s| s.report.ai_confidence).collect());
+    let satellite_evidence_hash = matching
+        .iter()
+        .find(|s| s.report.severity == severity)?
+        .report
+        .satellite_evidence_hash
+        .clone();
+
+    Some(AnomalyReport {
+        anomaly_type,
+        severity,
+        ai_confidence,
+        satellite_evidence_hash,
+    })
+}
+
+fn apply_progress_update(
+    project: &mut Project,
+    platform_state: &mut PlatformState,
+    report: ProgressReport,
+    current_time: i64,
+) -> Result<()> {
+    let newly_planted = report.trees_planted.saturating_sub(project.trees_planted);
+    let previous_co2 = project.co2_sequestered;
+
+    // Update project with AI-verified data; CO2 is the project's cumulative verified total
+    project.trees_planted = report.trees_planted;
+    project.co2_sequestered = report.co2_sequestered;
+    project.ai_health_score = report.ai_health_score;
+    project.ai_confidence = report.ai_confidence;
+    project.satellite_verification_hash = report.satellite_verification_hash.clone();
+    project.last_ai_update = current_time;
+
+    // Update platform state
+    platform_state.total_trees_planted = platform_state.total_trees_planted
+        .checked_add(newly_planted)
+        .ok_or(ErrorCode::MathOverflow)?;
+    // Vest newly verified CO2 across current funding; held back until there are investors
+    if report.co2_sequestered > project.co2_accrued && project.current_funding > 0 {
+        let vested = report.co2_sequestered - project.co2_accrued;
+        // The bonus pool only applies while some investor holds a bonus tier
+        let base_vested = if project.bonus_weight == 0 {
+            vested as u128
+        } else {
+            vested as u128 * (10000 - BONUS_POOL_BPS as u128) / 10000
+        };
+        project.credits_per_share = project.credits_per_share
+            .checked_add(base_vested * CREDITS_PRECISION / project.current_funding as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if let Some(bonus_share) = ((vested as u128 - base_vested) * CREDITS_PRECISION).checked_div(project.bonus_weight) {
+            project.bonus_per_weight = project.bonus_per_weight
+                .checked_add(bonus_share)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        project.co2_accrued = report.co2_sequestered;
+        emit!(CreditsVested {
+            project_id: project.id,
+            amount: vested,
+            co2_accrued: project.co2_accrued,
+            credits_per_share: project.credits_per_share,
+        });
+    }
+
+    platform_state.total_co2_offset = if report.co2_sequestered >= previous_co2 {
+        platform_state.total_co2_offset
+            .checked_add(report.co2_sequestered - previous_co2)
+            .ok_or(ErrorCode::MathOverflow)?
+    } else {
+        platform_state.total_co2_offset.saturating_sub(previous_co2 - report.co2_sequestered)
+    };
+
+    // Check if project is completed; while fundraising is open it can still expire below the minimum
+    if project.status == ProjectStatus::Funded && report.trees_planted >= project.target_trees {
+        project.status = ProjectStatus::Completed;
+    }
+
+    emit!(ProjectProgressUpdated {
+        project_id: project.id,
+        trees_planted: report.trees_planted,
+        ai_health_score: report.ai_health_score,
+        ai_confidence: report.ai_confidence,
+        satellite_hash: report.satellite_verification_hash,
+        co2_sequestered: report.co2_sequestered,
+    });
+
+    Ok(())
+}
 
 // Account structures
 #[derive(Accounts)]
-pub struct InitializePlatform<'info> {
+pub struct InitializePlatform<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<PlatformState>(),
+        seeds = [b"platform_state"],
+        bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(project_id: u64)]
+pub struct CreateProject<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Project::SPACE,
+        seeds = [b"project", project_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        init,
+        payer = creator,
+        token::mint = funding_mint,
+        token::authority = project,
+        seeds = [b"escrow", project.key().as_ref()],
+        bump
+    )]
+    pub project_escrow: Account<'info, TokenAccount>,
+    pub funding_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProjectMint<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = carbon_token_mint.decimals,
+        mint::authority = platform_state,
+        seeds = [b"project_mint", project.key().as_ref()],
+        bump
+    )]
+    pub project_mint: Account<'info, Mint>,
+    #[account(address = platform_state.carbon_token_mint)]
+    pub carbon_token_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProject<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    pub reviewer: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ProjectApprover as u8].as_ref(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct AssessProject<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    pub ai_oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RejectProject<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    pub reviewer: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ProjectApprover as u8].as_ref(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct InvestInProject<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + std::mem::size_of::<Investment>(),
+        seeds = [b"investment", project.key().as_ref(), investor.key().as_ref()],
+        bump
+    )]
+    pub investment: Account<'info, Investment>,
+    #[account(mut)]
+    pub investor: Signer<'info>,
+    #[account(mut)]
+    pub investor_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"escrow", project.key().as_ref()],
+        bump = project.escrow_bump
+    )]
+    pub project_escrow: Account<'info, TokenAccount>,
+    #[account(address = project.funding_mint)]
+    pub funding_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = investor,
+        token::mint = funding_mint,
+        token::authority = platform_state,
+        seeds = [b"fee_vault", funding_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProjectProgress<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    #[account(
+        init_if_needed,
+        payer = ai_oracle,
+        space = OracleRound::SPACE,
+        seeds = [b"oracle_round", project.key().as_ref()],
+        bump
+    )]
+    pub oracle_round: Account<'info, OracleRound>,
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", ai_oracle.key().as_ref()],
+        bump = oracle_stake.bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    #[account(mut)]
+    pub ai_oracle: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle: Pubkey)]
+pub struct SubmitProgressAttestation<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = OracleRound::SPACE,
+        seeds = [b"oracle_round", project.key().as_ref()],
+        bump
+    )]
+    pub oracle_round: Account<'info, OracleRound>,
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", oracle.as_ref()],
+        bump = oracle_stake.bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<AttestationNonce>(),
+        seeds = [b"attestation_nonce", project.key().as_ref(), oracle.as_ref()],
+        bump
+    )]
+    pub attestation_nonce: Account<'info, AttestationNonce>,
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    /// CHECK: address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProgressReport<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    #[account(
+        mut,
+        seeds = [b"oracle_round", project.key().as_ref()],
+        bump = oracle_round.bump
+    )]
+    pub oracle_round: Account<'info, OracleRound>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeProgressReport<'info> {
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    #[account(
+        mut,
+        seeds = [b"oracle_round", project.key().as_ref()],
+        bump = oracle_round.bump
+    )]
+    pub oracle_round: Account<'info, OracleRound>,
+    #[account(
+        init,
+        payer = challenger,
+        space = Dispute::SPACE,
+        seeds = [
+            b"dispute",
+            project.key().as_ref(),
+            oracle_round.pending_progress.as_ref().map(|pending| pending.round).unwrap_or_default().to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        init,
+        payer = challenger,
+        token::mint = carbon_token_mint,
+        token::authority = dispute,
+        seeds = [b"dispute_vault", dispute.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    #[account(address = platform_state.carbon_token_mint)]
+    pub carbon_token_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub challenger_token_account: Account<'info, TokenAccount>,
+    /// Required when challenging as an investor
+    pub investment: Option<Account<'info, Investment>>,
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"oracle_round", project.key().as_ref()],
+        bump = oracle_round.bump
+    )]
+    pub oracle_round: Account<'info, OracleRound>,
+    #[account(
+        mut,
+        seeds = [b"dispute", project.key().as_ref(), dispute.round.to_le_bytes().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        seeds = [b"dispute_vault", dispute.key().as_ref()],
+        bump = dispute.vault_bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = challenger_token_account.owner == dispute.challenger @ ErrorCode::InvalidTokenAccount,
+        constraint = challenger_token_account.mint == bond_vault.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub challenger_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::OracleManager as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseMilestoneFunds<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"escrow", project.key().as_ref()],
+        bump = project.escrow_bump
+    )]
+    pub project_escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == project.creator @ ErrorCode::UnauthorizedUpdate,
+        constraint = creator_token_account.mint == project.funding_mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCarbonCredits<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"investment", project.key().as_ref(), investor.key().as_ref()],
+        bump = investment.bump
+    )]
+    pub investment: Account<'info, Investment>,
+    #[account(
+        init,
+        payer = investor,
+        space = CreditBatch::SPACE,
+        seeds = [b"credit_batch", project.key().as_ref(), project.credit_batch_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub credit_batch: Account<'info, CreditBatch>,
+    #[account(
+        init,
+        payer = investor,
+        space = 8 + std::mem::size_of::<BatchHolding>(),
+        seeds = [b"batch_holding", credit_batch.key().as_ref(), investor.key().as_ref()],
+        bump
+    )]
+    pub batch_holding: Account<'info, BatchHolding>,
+    #[account(mut)]
+    pub investor: Signer<'info>,
+    #[account(mut, address = project.credit_token_mint(&platform_state))]
+    pub carbon_token_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub investor_carbon_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = investor,
+        token::mint = carbon_token_mint,
+        token::authority = platform_state,
+        seeds = [b"buffer", project.key().as_ref(), carbon_token_mint.key().as_ref()],
+        bump
+    )]
+    pub buffer_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferCredits<'info> {
+    pub platform_state: Account<'info, PlatformState>,
+    pub credit_batch: Account<'info, CreditBatch>,
+    #[account(
+        mut,
+        seeds = [b"batch_holding", credit_batch.key().as_ref(), sender.key().as_ref()],
+        bump = sender_holding.bump
+    )]
+    pub sender_holding: Account<'info, BatchHolding>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = 8 + std::mem::size_of::<BatchHolding>(),
+        seeds = [b"batch_holding", credit_batch.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_holding: Account<'info, BatchHolding>,
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    /// CHECK: Only used as the owner of the recipient holding and token account
+    #[account(constraint = recipient.key() != sender.key() @ ErrorCode::SelfTransfer)]
+    pub recipient: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = sender_carbon_account.owner == sender.key(),
+        constraint = sender_carbon_account.mint == credit_batch.mint
+    )]
+    pub sender_carbon_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = recipient_carbon_account.owner == recipient.key(),
+        constraint = recipient_carbon_account.mint == credit_batch.mint
+    )]
+    pub recipient_carbon_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RetireCredits<'info> {
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub credit_batch: Account<'info, CreditBatch>,
+    #[account(
+        mut,
+        seeds = [b"batch_holding", credit_batch.key().as_ref(), holder.key().as_ref()],
+        bump = batch_holding.bump
+    )]
+    pub batch_holding: Account<'info, BatchHolding>,
+    #[account(
+        init,
+        payer = holder,
+        space = RetirementCertificate::SPACE,
+        seeds = [b"retirement", platform_state.total_retirements.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub certificate: Account<'info, RetirementCertificate>,
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(mut, address = credit_batch.mint)]
+    pub carbon_token_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = holder_carbon_account.owner == holder.key(),
+        constraint = holder_carbon_account.mint == carbon_token_mint.key()
+    )]
+    pub holder_carbon_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveReversal<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [b"anomaly", project.key().as_ref(), anomaly.index.to_le_bytes().as_ref()],
+        bump = anomaly.bump
+    )]
+    pub anomaly: Account<'info, Anomaly>,
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"buffer", project.key().as_ref(), carbon_token_mint.key().as_ref()],
+        bump
+    )]
+    pub buffer_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub carbon_token_mint: Account<'info, Mint>,
+    pub reviewer: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ProjectApprover as u8].as_ref(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyReversalDebits<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+}
+
+#[derive(Accounts)]
+pub struct DeclareProjectFailed<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        seeds = [b"escrow", project.key().as_ref()],
+        bump = project.escrow_bump
+    )]
+    pub project_escrow: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ProjectApprover as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireProject<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [b"escrow", project.key().as_ref()],
+        bump = project.escrow_bump
+    )]
+    pub project_escrow: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"investment", project.key().as_ref(), investor.key().as_ref()],
+        bump = investment.bump
+    )]
+    pub investment: Account<'info, Investment>,
+    pub investor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"escrow", project.key().as_ref()],
+        bump = project.escrow_bump
+    )]
+    pub project_escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = investor_token_account.owner == investor.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = investor_token_account.mint == project.funding_mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub investor_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyAction<'info> {
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::Pauser as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct ManageAuthority<'info> {
+    #[account(mut, seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigAdminAction<'info> {
+    #[account(mut, seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ConfigAdmin as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureFeeSplit<'info> {
+    #[account(seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = FeeSplit::SPACE,
+        seeds = [b"fee_split"],
+        bump
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ConfigAdmin as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"fee_vault", fee_vault.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [b"fee_split"], bump = fee_split.bump)]
+    pub fee_split: Account<'info, FeeSplit>,
+    pub treasurer: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::Treasurer as u8].as_ref(), treasurer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, holder: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<RoleAssignment>(),
+        seeds = [b"role", [role as u8].as_ref(), holder.as_ref()],
+        bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+    #[account(seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"role", [role_assignment.role as u8].as_ref(), role_assignment.holder.as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+    #[account(seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Multisig::SPACE,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    #[account(mut, seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::SPACE,
+        seeds = [b"proposal", multisig.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut, seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut, seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigUpdate<'info> {
+    #[account(mut, seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut, seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAIOracle<'info> {
+    #[account(mut)]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::OracleManager as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOracleSet<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = OracleSet::SPACE,
+        seeds = [b"oracle_set"],
+        bump
+    )]
+    pub oracle_set: Account<'info, OracleSet>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::OracleManager as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOracleSet<'info> {
+    #[account(mut, seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::OracleManager as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct StakeOracle<'info> {
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + std::mem::size_of::<OracleStake>(),
+        seeds = [b"oracle_stake", oracle.key().as_ref()],
+        bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        token::mint = carbon_token_mint,
+        token::authority = oracle_stake,
+        seeds = [b"oracle_vault", oracle.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(address = platform_state.carbon_token_mint)]
+    pub carbon_token_mint: Account<'info, Mint>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub oracle_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", oracle.key().as_ref()],
+        bump = oracle_stake.bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", oracle.key().as_ref()],
+        bump = oracle_stake.bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    #[account(
+        mut,
+        seeds = [b"oracle_vault", oracle.key().as_ref()],
+        bump = oracle_stake.vault_bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    #[account(
+        mut,
+        constraint = oracle_token_account.owner == oracle.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub oracle_token_account: Account<'info, TokenAccount>,
+    pub oracle: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SlashOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", oracle_stake.oracle.as_ref()],
+        bump = oracle_stake.bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    #[account(
+        mut,
+        seeds = [b"oracle_vault", oracle_stake.oracle.as_ref()],
+        bump = oracle_stake.vault_bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    /// Slashed stake is paid out through withdraw_fees and the fee split
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = carbon_token_mint,
+        token::authority = platform_state,
+        seeds = [b"fee_vault", carbon_token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    #[account(address = platform_state.carbon_token_mint)]
+    pub carbon_token_mint: Account<'info, Mint>,
+    #[account(seeds = [b"platform_state"], bump = platform_state.bump)]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::OracleManager as u8].as_ref(), authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReportAnomaly<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    #[account(
+        init_if_needed,
+        payer = ai_oracle,
+        space = OracleRound::SPACE,
+        seeds = [b"oracle_round", project.key().as_ref()],
+        bump
+    )]
+    pub oracle_round: Account<'info, OracleRound>,
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", ai_oracle.key().as_ref()],
+        bump = oracle_stake.bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    /// CHECK: Created as an Anomaly by the submission that reaches quorum
+    #[account(
+        mut,
+        seeds = [b"anomaly", project.key().as_ref(), project.anomaly_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub anomaly: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub ai_oracle: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewAnomaly<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [b"anomaly", project.key().as_ref(), anomaly.index.to_le_bytes().as_ref()],
+        bump = anomaly.bump
+    )]
+    pub anomaly: Account<'info, Anomaly>,
+    pub reviewer: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ProjectApprover as u8].as_ref(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitCommunityReport<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        init,
+        payer = reporter,
+        space = CommunityReport::SPACE,
+        seeds = [b"community_report", project.key().as_ref(), project.community_report_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub report: Account<'info, CommunityReport>,
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmCommunityReport<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [b"community_report", project.key().as_ref(), report.index.to_le_bytes().as_ref()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, CommunityReport>,
     #[account(
         init,
-        payer = payer,
-        space = 8 + std::mem::size_of::<PlatformState>(),
-        seeds = [b"platform_state"],
+        payer = reviewer,
+        space = Anomaly::SPACE,
+        seeds = [b"anomaly", project.key().as_ref(), project.anomaly_count.to_le_bytes().as_ref()],
         bump
     )]
-    pub platform_state: Account<'info, PlatformState>,
+    pub anomaly: Account<'info, Anomaly>,
+    /// CHECK: Receives the returned bond and bounty; must be the report's submitter
+    #[account(mut, address = report.reporter)]
+    pub reporter: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"community_treasury"], bump)]
+    pub community_treasury: SystemAccount<'info>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub reviewer: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ProjectApprover as u8].as_ref(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectCommunityReport<'info> {
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [b"community_report", project.key().as_ref(), report.index.to_le_bytes().as_ref()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, CommunityReport>,
+    #[account(mut, seeds = [b"community_treasury"], bump)]
+    pub community_treasury: SystemAccount<'info>,
+    #[account(seeds = [b"oracle_set"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+    pub reviewer: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ProjectApprover as u8].as_ref(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct FundCommunityTreasury<'info> {
+    #[account(mut, seeds = [b"community_treasury"], bump)]
+    pub community_treasury: SystemAccount<'info>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FileAppeal<'info> {
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [b"anomaly", project.key().as_ref(), anomaly.index.to_le_bytes().as_ref()],
+        bump = anomaly.bump
+    )]
+    pub anomaly: Account<'info, Anomaly>,
+    #[account(
+        init,
+        payer = appellant,
+        space = Appeal::SPACE,
+        seeds = [b"appeal", anomaly.key().as_ref()],
+        bump
+    )]
+    pub appeal: Account<'info, Appeal>,
+    #[account(mut)]
+    pub appellant: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-#[derive(Accounts)]
-#[instruction(project_id: u64)]
-pub struct CreateProject<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + std::mem::size_of::<Project>(),
-        seeds = [b"project", project_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub project: Account<'info, Project>,
-    #[account(mut)]
-    pub platform_state: Account<'info, PlatformState>,
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    pub system_program: Program<'info, System>,
+#[derive(Accounts)]
+pub struct RuleOnAppeal<'info> {
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [b"anomaly", project.key().as_ref(), anomaly.index.to_le_bytes().as_ref()],
+        bump = anomaly.bump
+    )]
+    pub anomaly: Account<'info, Anomaly>,
+    #[account(
+        mut,
+        seeds = [b"appeal", anomaly.key().as_ref()],
+        bump = appeal.bump
+    )]
+    pub appeal: Account<'info, Appeal>,
+    pub reviewer: Signer<'info>,
+    #[account(
+        seeds = [b"role", [Role::ProjectApprover as u8].as_ref(), reviewer.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+// Data structures
+#[account]
+pub struct PlatformState {
+    pub authority: Pubkey,
+    pub carbon_token_mint: Pubkey,
+    pub ai_oracle_authority: Pubkey,
+    pub investment_fee_bps: u16,
+    pub min_ai_confidence: u8,
+    pub ai_update_interval: i64,
+    pub total_projects: u64,
+    pub total_investments: u64,
+    pub total_carbon_credits: u64,
+    pub total_trees_planted: u64,
+    pub total_co2_offset: u64,
+    pub emergency_pause: bool,
+    pub is_initialized: bool,
+    pub last_ai_update: i64,
+    pub platform_version: u8,
+    pub pending_authority: Pubkey,
+    pub pending_config: Option<PendingConfigUpdate>,
+    pub total_credits_retired: u64,
+    pub total_retirements: u64,
+    pub next_credit_serial: u64,
+    pub buffer_bps: u16,
+    pub bump: u8,
+}
+
+/// A fee split recipient; `wallet` owns the token account fees are paid into
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct FeeRecipient {
+    pub wallet: Pubkey,
+    pub bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PendingConfigUpdate {
+    pub investment_fee_bps: u16,
+    pub min_ai_confidence: u8,
+    pub ai_update_interval: i64,
+    pub buffer_bps: u16,
+    pub executable_at: i64,
+}
+
+#[account]
+pub struct Project {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    pub location: String,
+    pub creator: Pubkey,
+    pub target_trees: u64,
+    pub target_funding: u64,
+    pub current_funding: u64,
+    pub trees_planted: u64,
+    pub status: ProjectStatus,
+    pub created_at: i64,
+    pub ai_health_score: u8,
+    pub ai_confidence: u8,
+    pub ai_risk_assessment: u8,
+    pub satellite_verification_hash: String,
+    pub expected_co2_per_tree: u32,
+    pub project_tier: ProjectTier,
+    pub last_ai_update: i64,
+    pub carbon_credits_minted: u64,
+    pub total_investors: u32,
+    pub funding_mint: Pubkey,
+    pub milestones: Vec<Milestone>,
+    pub milestones_released: u8,
+    pub funds_released: u64,
+    pub refund_pool: u64,
+    pub funding_deadline: i64,
+    pub min_funding_bps: u16,
+    pub rejection_reason: u16,
+    pub assessed_at: i64,
+    pub co2_sequestered: u64,
+    pub bonus_credits_issued: u64,
+    pub co2_accrued: u64,
+    pub credits_per_share: u128,
+    pub bonus_weight: u128,
+    pub bonus_per_weight: u128,
+    pub methodology: String,
+    pub credit_batch_count: u64,
+    pub credit_mint: Pubkey,
+    pub buffer_credits: u64,
+    pub buffer_cancelled: u64,
+    pub anomaly_count: u64,
+    pub suspending_anomalies: u32,
+    pub status_before_suspension: Option<ProjectStatus>,
+    pub community_report_count: u64,
+    pub reversal_count: u32,
+    pub reversal_investor_loss: u64,
+    pub reversal_debited: u64,
+    pub reversal_investors_applied: u32,
+    pub total_reversed: u64,
+    pub escrow_bump: u8,
+    pub bump: u8,
+}
+
+impl Project {
+    /// Mint this project's credits are issued in
+    pub fn credit_token_mint(&self, platform_state: &PlatformState) -> Pubkey {
+        if self.credit_mint == Pubkey::default() {
+            platform_state.carbon_token_mint
+        } else {
+            self.credit_mint
+        }
+    }
+
+    pub const SPACE: usize = 8 // discriminator
+        + 8 // id
+        + 4 + MAX_NAME_LEN
+        + 4 + MAX_DESCRIPTION_LEN
+        + 4 + MAX_LOCATION_LEN
+        + 32 // creator
+        + 8 + 8 + 8 + 8 // targets, funding, trees planted
+        + 1 // status
+        + 8 // created_at
+        + 1 + 1 + 1 // AI scores
+        + 4 + SATELLITE_HASH_LEN
+        + 4 // expected_co2_per_tree
+        + 1 // project_tier
+        + 8 // last_ai_update
+        + 8 // carbon_credits_minted
+        + 4 // total_investors
+        + 32 // funding_mint
+        + 4 + MAX_MILESTONES * (8 + 2)
+        + 1 // milestones_released
+        + 8 // funds_released
+        + 8 // refund_pool
+        + 8 // funding_deadline
+        + 2 // min_funding_bps
+        + 2 // rejection_reason
+        + 8 // assessed_at
+        + 8 // co2_sequestered
+        + 8 // bonus_credits_issued
+        + 8 // co2_accrued
+        + 16 // credits_per_share
+        + 16 // bonus_weight
+        + 16 // bonus_per_weight
+        + 4 + MAX_METHODOLOGY_LEN
+        + 8 // credit_batch_count
+        + 32 // credit_mint
+        + 8 // buffer_credits
+        + 8 // buffer_cancelled
+        + 8 // anomaly_count
+        + 4 // suspending_anomalies
+        + 1 + 1 // status_before_suspension
+        + 8 // community_report_count
+        + 4 // reversal_count
+        + 8 // reversal_investor_loss
+        + 8 // reversal_debited
+        + 4 // reversal_investors_applied
+        + 8 // total_reversed
+        + 1 // escrow_bump
+        + 1; // bump
+}
+
+#[account]
+pub struct Investment {
+    pub project_id: u64,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub investment_tier: InvestmentTier,
+    pub bonus_multiplier: u16,
+    pub timestamp: i64,
+    pub is_active: bool,
+    pub is_refunded: bool,
+    pub carbon_credits_earned: u64,
+    pub credit_debt: u128,
+    pub bonus_weight: u128,
+    pub bonus_debt: u128,
+    pub deposit_count: u32,
+    pub reversals_applied: u32,
+    pub credits_reversed: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Multisig {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+impl Multisig {
+    pub const SPACE: usize = 8 + 4 + MAX_MULTISIG_SIGNERS * 32 + 1 + 8 + 1;
+}
+
+#[account]
+pub struct Proposal {
+    pub multisig: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const SPACE: usize = 8 + 32 + 8 + 32 + AdminAction::SPACE + 4 + MAX_MULTISIG_SIGNERS * 32 + 1 + 8 + 1;
+}
+
+#[account]
+pub struct RetirementCertificate {
+    pub sequence: u64,
+    pub retiree: Pubkey,
+    pub beneficiary_name: String,
+    pub reason: String,
+    pub project_id: u64,
+    pub batch: Pubkey,
+    pub vintage_year: u16,
+    pub serial_start: u64,
+    pub serial_end: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl RetirementCertificate {
+    pub const SPACE: usize = 8 + 8 + 32 + 4 + MAX_BENEFICIARY_LEN + 4 + MAX_RETIREMENT_REASON_LEN
+        + 8 + 32 + 2 + 8 + 8 + 8 + 8 + 1;
+}
+
+/// One issuance of carbon credits, numbered like a registry serial range (inclusive)
+#[account]
+pub struct CreditBatch {
+    pub project_id: u64,
+    pub mint: Pubkey,
+    pub index: u64,
+    pub vintage_year: u16,
+    pub serial_start: u64,
+    pub serial_end: u64,
+    pub amount: u64,
+    pub buffer_amount: u64,
+    pub retired: u64,
+    pub methodology: String,
+    pub verification_hash: String,
+    pub issued_at: i64,
+    pub bump: u8,
+}
+
+impl CreditBatch {
+    pub const SPACE: usize = 8 + 8 + 32 + 8 + 2 + 8 + 8 + 8 + 8 + 8 + 4 + MAX_METHODOLOGY_LEN + 4 + SATELLITE_HASH_LEN + 8 + 1;
+}
+
+/// Credits from a batch held by one owner. Tokens moved with a plain SPL transfer
+/// leave the holding behind, so batch-aware transfers go through transfer_credits.
+#[account]
+pub struct BatchHolding {
+    pub batch: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Anomaly {
+    pub project: Pubkey,
+    pub project_id: u64,
+    pub index: u64,
+    pub anomaly_type: AnomalyType,
+    pub severity: u8,
+    pub ai_confidence: u8,
+    pub evidence_hash: String,
+    pub reporter: Pubkey,
+    pub status: AnomalyStatus,
+    pub reported_at: i64,
+    pub closed_at: i64,
+    pub closed_by: Pubkey,
+    pub health_penalty: u8,
+    pub reversal_applied: bool,
+    pub bump: u8,
+}
+
+impl Anomaly {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 1 + 1 + 1 + 4 + SATELLITE_HASH_LEN + 32 + 1 + 8 + 8 + 32 + 1 + 1 + 1;
+}
+
+/// A community member's anomaly report; the bond is held as lamports on this account
+#[account]
+pub struct CommunityReport {
+    pub project: Pubkey,
+    pub project_id: u64,
+    pub index: u64,
+    pub reporter: Pubkey,
+    pub anomaly_type: AnomalyType,
+    pub evidence_hash: String,
+    pub bond: u64,
+    pub status: CommunityReportStatus,
+    pub submitted_at: i64,
+    pub reviewed_at: i64,
+    pub reviewer: Pubkey,
+    pub bump: u8,
+}
+
+impl CommunityReport {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 32 + 1 + 4 + SATELLITE_HASH_LEN + 8 + 1 + 8 + 8 + 32 + 1;
+}
+
+#[account]
+pub struct Appeal {
+    pub project: Pubkey,
+    pub anomaly: Pubkey,
+    pub appellant: Pubkey,
+    pub counter_evidence_hash: String,
+    pub status: AppealStatus,
+    pub filed_at: i64,
+    pub ruled_at: i64,
+    pub reviewer: Pubkey,
+    pub bump: u8,
+}
+
+impl Appeal {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 4 + SATELLITE_HASH_LEN + 1 + 8 + 8 + 32 + 1;
+}
+
+#[account]
+pub struct FeeSplit {
+    pub recipients: Vec<FeeRecipient>,
+    pub bump: u8,
+}
+
+impl FeeSplit {
+    pub const SPACE: usize = 8 + 4 + MAX_FEE_RECIPIENTS * (32 + 2) + 1;
+}
+
+#[account]
+pub struct RoleAssignment {
+    pub holder: Pubkey,
+    pub role: Role,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct OracleSet {
+    pub oracles: Vec<Pubkey>,
+    pub threshold: u8,
+    pub health_tolerance: u8,
+    pub trees_tolerance_bps: u16,
+    pub version: u32,
+    pub min_stake: u64,
+    pub unstake_cooldown: i64,
+    pub challenge_period: i64,
+    pub dispute_bond: u64,
+    pub bump: u8,
+}
+
+impl OracleSet {
+    pub const SPACE: usize = 8 + 4 + MAX_ORACLES * 32 + 1 + 1 + 2 + 4 + 8 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct OracleStake {
+    pub oracle: Pubkey,
+    pub amount: u64,
+    pub pending_unstake: u64,
+    pub unstake_requested_at: i64,
+    pub last_report_at: i64,
+    pub slashed_total: u64,
+    pub vault_bump: u8,
+    pub bump: u8,
+}
+
+/// Highest attestation nonce accepted from an oracle for a project
+#[account]
+pub struct AttestationNonce {
+    pub nonce: u64,
+    pub bump: u8,
+}
+
+/// Pending oracle submissions for a project, cleared whenever a round reaches quorum
+#[account]
+pub struct OracleRound {
+    pub project: Pubkey,
+    pub progress_round: u64,
+    pub progress_set_version: u32,
+    pub progress_submissions: Vec<ProgressSubmission>,
+    pub anomaly_round: u64,
+    pub anomaly_set_version: u32,
+    pub anomaly_submissions: Vec<AnomalySubmission>,
+    pub pending_progress: Option<PendingProgress>,
+    pub bump: u8,
+}
+
+impl OracleRound {
+    pub const SPACE: usize = 8
+        + 32 // project
+        + 8 + 4 + 4 + MAX_ORACLES * ProgressSubmission::SPACE
+        + 8 + 4 + 4 + MAX_ORACLES * AnomalySubmission::SPACE
+        + 1 + PendingProgress::SPACE
+        + 1; // bump
+}
+
+/// Quorum-agreed progress report waiting out its challenge period
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PendingProgress {
+    pub round: u64,
+    pub report: ProgressReport,
+    pub submitters: Vec<Pubkey>,
+    pub submitted_at: i64,
+    pub disputed: bool,
+    pub dispute_cleared: bool,
+}
+
+impl PendingProgress {
+    pub const SPACE: usize = 8 + ProgressReport::SPACE + 4 + MAX_ORACLES * 32 + 8 + 1 + 1;
+}
+
+#[account]
+pub struct Dispute {
+    pub project: Pubkey,
+    pub round: u64,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub evidence_hash: String,
+    pub created_at: i64,
+    pub resolved: bool,
+    pub challenger_won: bool,
+    pub vault_bump: u8,
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const SPACE: usize = 8 + 32 + 8 + 32 + 8 + 4 + SATELLITE_HASH_LEN + 8 + 1 + 1 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ProgressReport {
+    pub trees_planted: u64,
+    pub ai_health_score: u8,
+    pub ai_confidence: u8,
+    pub co2_sequestered: u64,
+    pub satellite_verification_hash: String,
+}
+
+impl ProgressReport {
+    pub const SPACE: usize = 8 + 1 + 1 + 8 + 4 + SATELLITE_HASH_LEN;
+}
+
+/// Payload an oracle signs off-chain; see attestation_message for the signed bytes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ProgressAttestation {
+    pub project_id: u64,
+    pub trees_planted: u64,
+    pub ai_health_score: u8,
+    pub ai_confidence: u8,
+    pub satellite_verification_hash: String,
+    pub co2_sequestered: u64,
+    pub timestamp: i64,
+    pub nonce: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ProgressSubmission {
+    pub oracle: Pubkey,
+    pub report: ProgressReport,
+}
+
+impl ProgressSubmission {
+    pub const SPACE: usize = 32 + ProgressReport::SPACE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AnomalyReport {
+    pub anomaly_type: AnomalyType,
+    pub severity: u8,
+    pub ai_confidence: u8,
+    pub satellite_evidence_hash: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AnomalySubmission {
+    pub oracle: Pubkey,
+    pub report: AnomalyReport,
+}
+
+impl AnomalySubmission {
+    pub const SPACE: usize = 32 + 1 + 1 + 1 + 4 + SATELLITE_HASH_LEN;
 }
 
-#[derive(Accounts)]
-pub struct InvestInProject<'info> {
-    #[account(mut)]
-    pub project: Account<'info, Project>,
-    #[account(mut)]
-    pub platform_state: Account<'info, PlatformState>,
-    #[account(
-        init_if_needed,
-        payer = investor,
-        space = 8 + std::mem::size_of::<Investment>(),
-        seeds = [b"investment", project.key().as_ref(), investor.key().as_ref()],
-        bump
-    )]
-    pub investment: Account<'info, Investment>,
-    #[account(mut)]
-    pub investor: Signer<'info>,
-    #[account(mut)]
-    pub investor_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub project_treasury: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub platform_treasury: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Milestone {
+    pub trees_target: u64,
+    pub release_bps: u16,
+}
+
+// Enums
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectStatus {
+    Active,
+    Funded,
+    Completed,
+    Suspended,
+    Failed,
+    PendingReview,
+    Rejected,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectTier {
+    Community,    // Small local projects
+    Regional,     // Medium regional projects
+    Enterprise,   // Large commercial projects
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum InvestmentTier {
+    Bronze,       // 1+ SOL
+    Silver,       // 5+ SOL
+    Gold,         // 10+ SOL
+    Platinum,     // 50+ SOL
+    Diamond,      // 100+ SOL
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyStatus {
+    Open,
+    Investigating,
+    Resolved,
+    Dismissed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CommunityReportStatus {
+    Pending,
+    Confirmed,
+    Rejected,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AppealStatus {
+    Pending,
+    Upheld,
+    Denied,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyType {
+    Deforestation,
+    IllegalLogging,
+    Fire,
+    Disease,
+    UnauthorizedAccess,
+    EnvironmentalDamage,
+}
+
+/// Admin operations a multisig proposal can carry out on the platform
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AdminAction {
+    Pause,
+    Resume,
+    UpdateAIOracle {
+        new_oracle: Pubkey,
+    },
+    QueueConfigUpdate {
+        investment_fee_bps: u16,
+        min_ai_confidence: u8,
+        ai_update_interval: i64,
+        buffer_bps: u16,
+    },
+    ProposeAuthority {
+        new_authority: Pubkey,
+    },
+    AcceptAuthority,
+    WithdrawFees {
+        mint: Pubkey,
+        amount: u64,
+    },
+    GrantRole {
+        role: Role,
+        holder: Pubkey,
+    },
+    RevokeRole {
+        role: Role,
+        holder: Pubkey,
+    },
+    AddSigner {
+        signer: Pubkey,
+    },
+    RemoveSigner {
+        signer: Pubkey,
+    },
+    ChangeThreshold {
+        threshold: u8,
+    },
+}
+
+impl AdminAction {
+    pub const SPACE: usize = 1 + 32 + 8; // Tag plus the largest variant
+}
+
+/// Least-privilege operational roles granted by the platform authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Pauser,          // Emergency pause and resume
+    ConfigAdmin,     // Platform fee and threshold changes
+    OracleManager,   // Oracle set, staking, slashing and disputes
+    ProjectApprover, // Project lifecycle decisions
+    Treasurer,       // Platform fee withdrawals
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OracleReportKind {
+    Progress,
+    Anomaly,
+}
+
+// Events
+#[event]
+pub struct PlatformInitialized {
+    pub authority: Pubkey,
+    pub carbon_token_mint: Pubkey,
+    pub ai_oracle_authority: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+pub struct ProjectCreated {
+    pub project_id: u64,
+    pub creator: Pubkey,
+    pub target_trees: u64,
+    pub target_funding: u64,
+    pub project_tier: ProjectTier,
+    pub satellite_hash: String,
+}
+
+#[event]
+pub struct ProjectAssessed {
+    pub project_id: u64,
+    pub ai_oracle: Pubkey,
+    pub ai_health_score: u8,
+    pub ai_confidence: u8,
+    pub ai_risk_assessment: u8,
+}
+
+#[event]
+pub struct ProjectApproved {
+    pub project_id: u64,
+    pub reviewer: Pubkey,
+    pub ai_health_score: u8,
+    pub ai_confidence: u8,
+    pub ai_risk_assessment: u8,
+}
+
+#[event]
+pub struct ProjectRejected {
+    pub project_id: u64,
+    pub reviewer: Pubkey,
+    pub reason_code: u16,
+}
+
+#[event]
+pub struct InvestmentMade {
+    pub project_id: u64,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub total_amount: u64,
+    pub deposit_index: u32,
+    pub investment_tier: InvestmentTier,
+    pub bonus_multiplier: u16,
+    pub ai_bonus: u16,
+}
+
+#[event]
+pub struct ProjectProgressUpdated {
+    pub project_id: u64,
+    pub trees_planted: u64,
+    pub ai_health_score: u8,
+    pub ai_confidence: u8,
+    pub satellite_hash: String,
+    pub co2_sequestered: u64,
+}
+
+#[event]
+pub struct MilestoneFundsReleased {
+    pub project_id: u64,
+    pub milestone_index: u8,
+    pub trees_target: u64,
+    pub amount: u64,
+    pub total_released: u64,
+}
+
+#[event]
+pub struct ProjectFailed {
+    pub project_id: u64,
+    pub refund_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProjectExpired {
+    pub project_id: u64,
+    pub current_funding: u64,
+    pub min_funding: u64,
+    pub failed: bool,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub project_id: u64,
+    pub investor: Pubkey,
+    pub invested_amount: u64,
+    pub refund_amount: u64,
+}
+
+#[event]
+pub struct ReversalResolved {
+    pub project_id: u64,
+    pub reversal: u32,
+    pub anomaly: Pubkey,
+    pub anomaly_type: AnomalyType,
+    pub tonnes_lost: u64,
+    pub buffer_debit: u64,
+    pub investor_loss: u64,
+}
+
+#[event]
+pub struct CreditsReversed {
+    pub project_id: u64,
+    pub reversal: u32,
+    pub investment: Pubkey,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub unclaimed_remaining: u64,
+}
+
+#[event]
+pub struct ProjectMintCreated {
+    pub project_id: u64,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct CreditsRetired {
+    pub sequence: u64,
+    pub retiree: Pubkey,
+    pub beneficiary_name: String,
+    pub project_id: u64,
+    pub batch: Pubkey,
+    pub serial_start: u64,
+    pub serial_end: u64,
+    pub amount: u64,
+    pub total_retired: u64,
+}
+
+#[event]
+pub struct CreditsTransferred {
+    pub batch: Pubkey,
+    pub project_id: u64,
+    pub vintage_year: u16,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreditsVested {
+    pub project_id: u64,
+    pub amount: u64,
+    pub co2_accrued: u64,
+    pub credits_per_share: u128,
+}
+
+#[event]
+pub struct CarbonCreditsClaimed {
+    pub user: Pubkey,
+    pub project_id: u64,
+    pub amount: u64,
+    pub total_offset: u64,
+    pub investment_tier: InvestmentTier,
+    pub bonus_credits: u64,
+    pub buffer_credits: u64,
+    pub batch: Pubkey,
+    pub serial_start: u64,
+    pub serial_end: u64,
+}
+
+#[event]
+pub struct EmergencyPause {
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyResume {
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub authority: Pubkey,
+    pub proposed_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+}
+
+#[event]
+pub struct ConfigUpdateQueued {
+    pub old_investment_fee_bps: u16,
+    pub new_investment_fee_bps: u16,
+    pub old_min_ai_confidence: u8,
+    pub new_min_ai_confidence: u8,
+    pub old_ai_update_interval: i64,
+    pub new_ai_update_interval: i64,
+    pub old_buffer_bps: u16,
+    pub new_buffer_bps: u16,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct PlatformConfigUpdated {
+    pub old_investment_fee_bps: u16,
+    pub new_investment_fee_bps: u16,
+    pub old_min_ai_confidence: u8,
+    pub new_min_ai_confidence: u8,
+    pub old_ai_update_interval: i64,
+    pub new_ai_update_interval: i64,
+    pub old_buffer_bps: u16,
+    pub new_buffer_bps: u16,
+}
+
+#[event]
+pub struct ConfigUpdateCancelled {
+    pub investment_fee_bps: u16,
+    pub min_ai_confidence: u8,
+    pub ai_update_interval: i64,
+    pub buffer_bps: u16,
+}
+
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct FeeSplitConfigured {
+    pub recipients: Vec<Pubkey>,
+    pub bps: Vec<u16>,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub recipients: Vec<Pubkey>,
+    pub amounts: Vec<u64>,
+    pub withdrawn_by: Pubkey,
+}
+
+#[event]
+pub struct MultisigUpdated {
+    pub multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
 }
 
-#[derive(Accounts)]
-pub struct UpdateProjectProgress<'info> {
-    #[account(mut)]
-    pub project: Account<'info, Project>,
-    #[account(mut)]
-    pub platform_state: Account<'info, PlatformState>,
-    pub ai_oracle: Signer<'info>,
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+    pub action: AdminAction,
 }
 
-#[derive(Accounts)]
-pub struct ClaimCarbonCredits<'info> {
-    pub project: Account<'info, Project>,
-    #[account(mut)]
-    pub platform_state: Account<'info, PlatformState>,
-    #[account(mut)]
-    pub investment: Account<'info, Investment>,
-    pub investor: Signer<'info>,
-    #[account(mut)]
-    pub carbon_token_mint: Account<'info, Mint>,
-    #[account(mut)]
-    pub investor_carbon_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted_by: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct EmergencyAction<'info> {
-    #[account(mut)]
-    pub platform_state: Account<'info, PlatformState>,
-    pub authority: Signer<'info>,
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub revoked_by: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct UpdateAIOracle<'info> {
-    #[account(mut)]
-    pub platform_state: Account<'info, PlatformState>,
-    pub authority: Signer<'info>,
+#[event]
+pub struct AIOracleUpdated {
+    pub old_oracle: Pubkey,
+    pub new_oracle: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct ReportAnomaly<'info> {
-    #[account(mut)]
-    pub project: Account<'info, Project>,
-    pub platform_state: Account<'info, PlatformState>,
-    pub ai_oracle: Signer<'info>,
+#[event]
+pub struct OracleSetUpdated {
+    pub oracles: Vec<Pubkey>,
+    pub threshold: u8,
+    pub health_tolerance: u8,
+    pub trees_tolerance_bps: u16,
+    pub version: u32,
 }
 
-// Data structures
-#[account]
-pub struct PlatformState {
-    pub authority: Pubkey,
-    pub carbon_token_mint: Pubkey,
-    pub ai_oracle_authority: Pubkey,
-    pub investment_fee_bps: u16,
-    pub min_ai_confidence: u8,
-    pub ai_update_interval: i64,
-    pub total_projects: u64,
-    pub total_investments: u64,
-    pub total_carbon_credits: u64,
-    pub total_trees_planted: u64,
-    pub total_co2_offset: u64,
-    pub emergency_pause: bool,
-    pub is_initialized: bool,
-    pub last_ai_update: i64,
-    pub platform_version: u8,
-    pub bump: u8,
+#[event]
+pub struct OracleReportSubmitted {
+    pub project_id: u64,
+    pub oracle: Pubkey,
+    pub kind: OracleReportKind,
+    pub round: u64,
+    pub submissions: u8,
 }
 
-#[account]
-pub struct Project {
-    pub id: u64,
-    pub name: String,
-    pub description: String,
-    pub location: String,
-    pub creator: Pubkey,
-    pub target_trees: u64,
-    pub target_funding: u64,
-    pub current_funding: u64,
+#[event]
+pub struct AttestationSubmitted {
+    pub project_id: u64,
+    pub oracle: Pubkey,
+    pub nonce: u64,
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct DisputeWindowConfigured {
+    pub challenge_period: i64,
+    pub dispute_bond: u64,
+}
+
+#[event]
+pub struct ProgressReportPending {
+    pub project_id: u64,
+    pub round: u64,
     pub trees_planted: u64,
-    pub status: ProjectStatus,
-    pub created_at: i64,
-    pub ai_health_score: u8,
-    pub ai_confidence: u8,
-    pub ai_risk_assessment: u8,
-    pub satellite_verification_hash: String,
-    pub expected_co2_per_tree: u32,
-    pub project_tier: ProjectTier,
-    pub last_ai_update: i64,
-    pub carbon_credits_minted: u64,
-    pub total_investors: u32,
-    pub bump: u8,
+    pub co2_sequestered: u64,
+    pub challenge_ends_at: i64,
 }
 
-#[account]
-pub struct Investment {
+#[event]
+pub struct ProgressReportDisputed {
     pub project_id: u64,
-    pub investor: Pubkey,
-    pub amount: u64,
-    pub investment_tier: InvestmentTier,
-    pub bonus_multiplier: u16,
-    pub timestamp: i64,
-    pub is_active: bool,
-    pub carbon_credits_earned: u64,
-    pub bump: u8,
+    pub round: u64,
+    pub challenger: Pubkey,
+    pub bond: u64,
 }
 
-// Enums
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum ProjectStatus {
-    Active,
-    Funded,
-    Completed,
-    Suspended,
+#[event]
+pub struct DisputeResolved {
+    pub project_id: u64,
+    pub round: u64,
+    pub dispute: Pubkey,
+    pub challenger: Pubkey,
+    pub challenger_won: bool,
+    pub bond: u64,
+    pub slashed: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum ProjectTier {
-    Community,    // Small local projects
-    Regional,     // Medium regional projects
-    Enterprise,   // Large commercial projects
+#[event]
+pub struct ProgressReportDiscarded {
+    pub project_id: u64,
+    pub round: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum InvestmentTier {
-    Bronze,       // 1+ SOL
-    Silver,       // 5+ SOL
-    Gold,         // 10+ SOL
-    Platinum,     // 50+ SOL
-    Diamond,      // 100+ SOL
+#[event]
+pub struct OracleRoundFailed {
+    pub project_id: u64,
+    pub kind: OracleReportKind,
+    pub round: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum AnomalyType {
-    Deforestation,
-    IllegalLogging,
-    Fire,
-    Disease,
-    UnauthorizedAccess,
-    EnvironmentalDamage,
+#[event]
+pub struct OracleStakingConfigured {
+    pub min_stake: u64,
+    pub unstake_cooldown: i64,
 }
 
-// Events
 #[event]
-pub struct PlatformInitialized {
-    pub authority: Pubkey,
-    pub carbon_token_mint: Pubkey,
-    pub ai_oracle_authority: Pubkey,
-    pub version: u8,
+pub struct OracleStaked {
+    pub oracle: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
 }
 
 #[event]
-pub struct ProjectCreated {
-    pub project_id: u64,
-    pub creator: Pubkey,
-    pub target_trees: u64,
-    pub target_funding: u64,
-    pub ai_health_score: u8,
-    pub ai_confidence: u8,
-    pub project_tier: ProjectTier,
-    pub satellite_hash: String,
+pub struct OracleUnstakeRequested {
+    pub oracle: Pubkey,
+    pub amount: u64,
+    pub pending_unstake: u64,
+    pub withdrawable_at: i64,
 }
 
 #[event]
-pub struct InvestmentMade {
+pub struct OracleStakeWithdrawn {
+    pub oracle: Pubkey,
+    pub amount: u64,
+    pub remaining_stake: u64,
+}
+
+#[event]
+pub struct OracleSlashed {
+    pub oracle: Pubkey,
     pub project_id: u64,
-    pub investor: Pubkey,
     pub amount: u64,
-    pub investment_tier: InvestmentTier,
-    pub bonus_multiplier: u16,
-    pub ai_bonus: u16,
+    pub remaining_stake: u64,
 }
 
 #[event]
-pub struct ProjectProgressUpdated {
+pub struct AnomalyStatusChanged {
     pub project_id: u64,
-    pub trees_planted: u64,
-    pub ai_health_score: u8,
-    pub ai_confidence: u8,
-    pub satellite_hash: String,
-    pub co2_sequestered: u64,
+    pub anomaly: Pubkey,
+    pub status: AnomalyStatus,
+    pub reviewer: Pubkey,
+    pub restored_status: Option<ProjectStatus>,
 }
 
 #[event]
-pub struct CarbonCreditsClaimed {
-    pub user: Pubkey,
+pub struct CommunityReportSubmitted {
     pub project_id: u64,
+    pub report: Pubkey,
+    pub reporter: Pubkey,
+    pub anomaly_type: AnomalyType,
+    pub evidence_hash: String,
+    pub bond: u64,
+}
+
+#[event]
+pub struct CommunityTreasuryFunded {
+    pub funder: Pubkey,
     pub amount: u64,
-    pub total_offset: u64,
-    pub investment_tier: InvestmentTier,
-    pub ai_health_bonus: u64,
+    pub balance: u64,
 }
 
 #[event]
-pub struct EmergencyPause {
-    pub timestamp: i64,
+pub struct CommunityReportReviewed {
+    pub project_id: u64,
+    pub report: Pubkey,
+    pub reporter: Pubkey,
+    pub reviewer: Pubkey,
+    pub confirmed: bool,
+    pub bond: u64,
+    pub bounty: u64,
 }
 
 #[event]
-pub struct EmergencyResume {
-    pub timestamp: i64,
+pub struct AppealFiled {
+    pub project_id: u64,
+    pub anomaly: Pubkey,
+    pub appeal: Pubkey,
+    pub appellant: Pubkey,
+    pub counter_evidence_hash: String,
 }
 
 #[event]
-pub struct AIOracleUpdated {
-    pub old_oracle: Pubkey,
-    pub new_oracle: Pubkey,
+pub struct AppealRuled {
+    pub project_id: u64,
+    pub anomaly: Pubkey,
+    pub appeal: Pubkey,
+    pub reviewer: Pubkey,
+    pub upheld: bool,
+    pub restored_status: Option<ProjectStatus>,
+    pub ai_health_score: u8,
 }
 
 #[event]
 pub struct AnomalyDetected {
     pub project_id: u64,
+    pub anomaly: Pubkey,
+    pub index: u64,
     pub anomaly_type: AnomalyType,
     pub severity: u8,
     pub ai_confidence: u8,
@@ -739,10 +5120,460 @@ pub enum ErrorCode {
     PlatformPaused,
     #[msg("Unauthorized emergency action")]
     UnauthorizedEmergency,
+    #[msg("Only the platform authority can perform this action")]
+    UnauthorizedAuthority,
     #[msg("AI update too frequent")]
     TooFrequentAIUpdate,
     #[msg("Math overflow")]
     MathOverflow,
     #[msg("Invalid severity level")]
     InvalidSeverity,
-}
\ No newline at end of file
+    #[msg("Location is too long")]
+    LocationTooLong,
+    #[msg("Invalid milestone schedule")]
+    InvalidMilestones,
+    #[msg("No new milestone reached")]
+    NoMilestoneReached,
+    #[msg("Project is suspended")]
+    ProjectSuspended,
+    #[msg("Invalid token account")]
+    InvalidTokenAccount,
+    #[msg("Project has not failed")]
+    ProjectNotFailed,
+    #[msg("Investment has already been refunded")]
+    InvestmentRefunded,
+    #[msg("Invalid funding deadline")]
+    InvalidFundingDeadline,
+    #[msg("Invalid minimum funding threshold")]
+    InvalidMinFunding,
+    #[msg("Funding deadline has passed")]
+    FundingDeadlinePassed,
+    #[msg("Funding deadline has not been reached")]
+    FundingDeadlineNotReached,
+    #[msg("Invalid oracle set configuration")]
+    InvalidOracleSet,
+    #[msg("Oracle already submitted a report this round")]
+    DuplicateOracleSubmission,
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount,
+    #[msg("Oracle stake below the required minimum")]
+    InsufficientOracleStake,
+    #[msg("Unstake cooldown has not elapsed")]
+    UnstakeCooldownActive,
+    #[msg("Missing or invalid Ed25519 attestation signature")]
+    InvalidAttestationSignature,
+    #[msg("Attestation is stale or from the future")]
+    StaleAttestation,
+    #[msg("Attestation nonce already used")]
+    AttestationReplayed,
+    #[msg("A progress report is already awaiting finalization")]
+    ProgressReportPending,
+    #[msg("No pending progress report")]
+    NoPendingProgressReport,
+    #[msg("Challenge period is still active")]
+    ChallengePeriodActive,
+    #[msg("Challenge period has ended")]
+    ChallengePeriodEnded,
+    #[msg("Progress report is under dispute")]
+    ReportAlreadyDisputed,
+    #[msg("Only the creator, an investor or an oracle can dispute")]
+    UnauthorizedChallenger,
+    #[msg("Dispute already resolved")]
+    DisputeAlreadyResolved,
+    #[msg("Bond recipients do not match the reporting oracles")]
+    InvalidBondRecipients,
+    #[msg("Invalid authority")]
+    InvalidAuthority,
+    #[msg("No pending authority transfer")]
+    NoPendingAuthority,
+    #[msg("Invalid configuration value")]
+    InvalidConfigValue,
+    #[msg("No pending configuration update")]
+    NoPendingConfigUpdate,
+    #[msg("Configuration timelock has not elapsed")]
+    ConfigTimelockActive,
+    #[msg("Invalid multisig configuration")]
+    InvalidMultisig,
+    #[msg("Signer is not a multisig member")]
+    NotMultisigSigner,
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Signer already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal does not have enough approvals")]
+    InsufficientApprovals,
+    #[msg("Project is not pending review")]
+    ProjectNotPendingReview,
+    #[msg("Project has no oracle assessment")]
+    ProjectNotAssessed,
+    #[msg("Invalid AI assessment values")]
+    InvalidAssessment,
+    #[msg("No verified carbon credits available")]
+    NoVerifiedCredits,
+    #[msg("Project is not vesting carbon credits")]
+    ProjectNotVesting,
+    #[msg("Retirement amount must be greater than zero")]
+    InvalidRetirementAmount,
+    #[msg("Beneficiary name too long")]
+    BeneficiaryNameTooLong,
+    #[msg("Retirement reason too long")]
+    RetirementReasonTooLong,
+    #[msg("Methodology too long")]
+    MethodologyTooLong,
+    #[msg("Transfer amount must be greater than zero")]
+    InvalidTransferAmount,
+    #[msg("Insufficient credits held in this batch")]
+    InsufficientBatchBalance,
+    #[msg("Cannot transfer credits to yourself")]
+    SelfTransfer,
+    #[msg("Only the project creator can perform this action")]
+    UnauthorizedProjectCreator,
+    #[msg("Project already has its own credit mint")]
+    ProjectMintExists,
+    #[msg("Project has already issued credits")]
+    CreditsAlreadyIssued,
+    #[msg("Cancellation amount must be greater than zero")]
+    InvalidCancellationAmount,
+    #[msg("Reversal exceeds the project's verified CO2")]
+    ReversalExceedsSequestered,
+    #[msg("No fire or deforestation anomaly to cover")]
+    NoReversalAnomaly,
+    #[msg("Reversal accounts must be writable investments")]
+    InvalidReversalAccount,
+    #[msg("Reversal already applied to this investment")]
+    ReversalAlreadyApplied,
+    #[msg("An earlier reversal has not been applied to every investment yet")]
+    ReversalPending,
+    #[msg("Anomaly is not open")]
+    AnomalyNotOpen,
+    #[msg("Anomaly is already closed")]
+    AnomalyClosed,
+    #[msg("Anomaly has already been charged against credits")]
+    AnomalyReversalApplied,
+    #[msg("Project is not suspended")]
+    ProjectNotSuspended,
+    #[msg("Anomaly did not suspend the project")]
+    AnomalyNotSuspending,
+    #[msg("Appeal has already been ruled on")]
+    AppealNotPending,
+    #[msg("Community report has already been reviewed")]
+    CommunityReportNotPending,
+    #[msg("Only an oracle or project approver can review community reports")]
+    UnauthorizedCommunityReviewer,
+    #[msg("Reporters cannot review their own community reports")]
+    SelfReview,
+    #[msg("Fundraising has not closed")]
+    FundraisingNotClosed,
+    #[msg("Role assignment account does not match the proposal")]
+    InvalidRoleAccount,
+    #[msg("Fee split must have 1-5 distinct recipients with basis points summing to 10000")]
+    InvalidFeeSplit,
+    #[msg("Fee recipients do not match the fee split")]
+    InvalidFeeRecipients,
+    #[msg("Insufficient fees in the vault")]
+    InsufficientFees,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_project() -> Project {
+        Project {
+            id: 1,
+            name: String::new(),
+            description: String::new(),
+            location: String::new(),
+            creator: Pubkey::new_unique(),
+            target_trees: 1_000_000,
+            target_funding: 1_000_000_000,
+            current_funding: 0,
+            trees_planted: 0,
+            status: ProjectStatus::Active,
+            created_at: 0,
+            ai_health_score: 80,
+            ai_confidence: 90,
+            ai_risk_assessment: 0,
+            satellite_verification_hash: String::new(),
+            expected_co2_per_tree: 1,
+            project_tier: ProjectTier::Community,
+            last_ai_update: 0,
+            carbon_credits_minted: 0,
+            total_investors: 0,
+            funding_mint: Pubkey::new_unique(),
+            milestones: Vec::new(),
+            milestones_released: 0,
+            funds_released: 0,
+            refund_pool: 0,
+            funding_deadline: 0,
+            min_funding_bps: 0,
+            rejection_reason: 0,
+            assessed_at: 0,
+            co2_sequestered: 0,
+            bonus_credits_issued: 0,
+            co2_accrued: 0,
+            credits_per_share: 0,
+            bonus_weight: 0,
+            bonus_per_weight: 0,
+            methodology: String::new(),
+            credit_batch_count: 0,
+            credit_mint: Pubkey::default(),
+            buffer_credits: 0,
+            buffer_cancelled: 0,
+            anomaly_count: 0,
+            suspending_anomalies: 0,
+            status_before_suspension: None,
+            community_report_count: 0,
+            reversal_count: 0,
+            reversal_investor_loss: 0,
+            reversal_debited: 0,
+            reversal_investors_applied: 0,
+            total_reversed: 0,
+            escrow_bump: 0,
+            bump: 0,
+        }
+    }
+
+    fn test_investment() -> Investment {
+        Investment {
+            project_id: 1,
+            investor: Pubkey::new_unique(),
+            amount: 0,
+            investment_tier: InvestmentTier::Bronze,
+            bonus_multiplier: 0,
+            timestamp: 0,
+            is_active: true,
+            is_refunded: false,
+            carbon_credits_earned: 0,
+            credit_debt: 0,
+            bonus_weight: 0,
+            bonus_debt: 0,
+            deposit_count: 0,
+            reversals_applied: 0,
+            credits_reversed: 0,
+            bump: 0,
+        }
+    }
+
+    fn test_platform_state() -> PlatformState {
+        PlatformState {
+            authority: Pubkey::new_unique(),
+            carbon_token_mint: Pubkey::new_unique(),
+            ai_oracle_authority: Pubkey::new_unique(),
+            investment_fee_bps: 0,
+            min_ai_confidence: 0,
+            ai_update_interval: 0,
+            total_projects: 0,
+            total_investments: 0,
+            total_carbon_credits: 0,
+            total_trees_planted: 0,
+            total_co2_offset: 0,
+            emergency_pause: false,
+            is_initialized: true,
+            last_ai_update: 0,
+            platform_version: 1,
+            pending_authority: Pubkey::default(),
+            pending_config: None,
+            total_credits_retired: 0,
+            total_retirements: 0,
+            next_credit_serial: 1,
+            buffer_bps: DEFAULT_BUFFER_BPS,
+            bump: 0,
+        }
+    }
+
+    fn progress_report(trees_planted: u64, co2_sequestered: u64) -> ProgressReport {
+        ProgressReport {
+            trees_planted,
+            ai_health_score: 80,
+            ai_confidence: 90,
+            co2_sequestered,
+            satellite_verification_hash: "a".repeat(SATELLITE_HASH_LEN),
+        }
+    }
+
+    fn verify_co2(project: &mut Project, platform_state: &mut PlatformState, co2_sequestered: u64) {
+        apply_progress_update(project, platform_state, progress_report(0, co2_sequestered), 0).unwrap();
+    }
+
+    #[test]
+    fn staggered_investors_only_vest_credits_verified_after_they_join() {
+        let mut project = test_project();
+        let mut platform_state = test_platform_state();
+        let mut early = test_investment();
+        let mut late = test_investment();
+
+        record_deposit(&mut project, &mut early, 1_000_000, 100).unwrap();
+        verify_co2(&mut project, &mut platform_state, 100);
+        record_deposit(&mut project, &mut late, 1_000_000, 100).unwrap();
+        verify_co2(&mut project, &mut platform_state, 300);
+
+        assert_eq!(settle_claim(&mut project, &mut late), (100, 0));
+        assert_eq!(settle_claim(&mut project, &mut early), (200, 0));
+        assert_eq!(project.carbon_credits_minted, 300);
+        // Nothing is left to claim until more CO2 is verified
+        assert_eq!(settle_claim(&mut project, &mut early), (0, 0));
+    }
+
+    #[test]
+    fn bonus_pool_is_shared_pro_rata_by_weight_regardless_of_claim_order() {
+        let mut project = test_project();
+        let mut platform_state = test_platform_state();
+        let mut base_only = test_investment();
+        let mut platinum = test_investment();
+        let mut gold = test_investment();
+
+        record_deposit(&mut project, &mut base_only, 1_000_000, 100).unwrap();
+        record_deposit(&mut project, &mut platinum, 1_000_000, 150).unwrap();
+        record_deposit(&mut project, &mut gold, 1_000_000, 125).unwrap();
+        verify_co2(&mut project, &mut platform_state, 1_000);
+
+        // 800 base credits split three ways; the 200 bonus credits split 2:1 by weight
+        assert_eq!(settle_claim(&mut project, &mut gold), (266 + 66, 66));
+        assert_eq!(settle_claim(&mut project, &mut base_only), (266, 0));
+        assert_eq!(settle_claim(&mut project, &mut platinum), (266 + 133, 133));
+        assert!(project.carbon_credits_minted <= project.co2_accrued);
+    }
+
+    #[test]
+    fn without_bonus_holders_the_whole_vest_goes_to_base_credits() {
+        let mut project = test_project();
+        let mut platform_state = test_platform_state();
+        let mut investment = test_investment();
+
+        record_deposit(&mut project, &mut investment, 1_000_000, 100).unwrap();
+        verify_co2(&mut project, &mut platform_state, 500);
+
+        assert_eq!(settle_claim(&mut project, &mut investment), (500, 0));
+    }
+
+    #[test]
+    fn top_up_never_shrinks_bonus_weight_or_pending_bonus() {
+        let mut project = test_project();
+        let mut platform_state = test_platform_state();
+        let mut investment = test_investment();
+        let mut other = test_investment();
+
+        record_deposit(&mut project, &mut investment, 3_000_000, 111).unwrap();
+        record_deposit(&mut project, &mut other, 3_000_000, 111).unwrap();
+        verify_co2(&mut project, &mut platform_state, 1_000);
+        let pending_bonus = investment.bonus_weight * project.bonus_per_weight - investment.bonus_debt;
+
+        record_deposit(&mut project, &mut investment, 1_000_000, 100).unwrap();
+
+        // The stored average truncates to 108, but the weight keeps each deposit's exact share
+        assert_eq!(investment.bonus_multiplier, 108);
+        assert_eq!(investment.bonus_weight, 33_000_000);
+        assert_eq!(project.bonus_weight, 66_000_000);
+        assert_eq!(investment.bonus_weight * project.bonus_per_weight - investment.bonus_debt, pending_bonus);
+    }
+
+    #[test]
+    fn credits_cut_off_by_the_mint_cap_stay_owed() {
+        let mut project = test_project();
+        let mut platform_state = test_platform_state();
+        let mut first = test_investment();
+        let mut second = test_investment();
+
+        record_deposit(&mut project, &mut first, 1_000_000, 100).unwrap();
+        record_deposit(&mut project, &mut second, 1_000_000, 100).unwrap();
+        verify_co2(&mut project, &mut platform_state, 100);
+        assert_eq!(settle_claim(&mut project, &mut first), (50, 0));
+
+        // A reversal lowers what has been verified below what the accumulator promised
+        project.co2_accrued -= 20;
+        assert_eq!(settle_claim(&mut project, &mut second), (30, 0));
+
+        // Once more CO2 is verified the shortfall is paid on top of the new vest
+        verify_co2(&mut project, &mut platform_state, 180);
+        assert_eq!(settle_claim(&mut project, &mut second), (70, 0));
+        // The 20 tonnes lost still leave someone short, but never past what was verified
+        assert_eq!(settle_claim(&mut project, &mut first), (30, 0));
+        assert_eq!(project.carbon_credits_minted, project.co2_accrued);
+    }
+
+    #[test]
+    fn median_picks_the_lower_middle_value() {
+        assert_eq!(median(vec![3, 1, 2]), 2);
+        assert_eq!(median(vec![4, 1, 3, 2]), 2);
+        assert_eq!(median(vec![7]), 7);
+    }
+
+    #[test]
+    fn within_bps_is_inclusive_of_the_tolerance() {
+        assert!(within_bps(105, 100, 500));
+        assert!(within_bps(95, 100, 500));
+        assert!(!within_bps(106, 100, 500));
+        assert!(within_bps(0, 0, 0));
+        assert!(!within_bps(1, 0, 10000));
+    }
+
+    fn test_oracle_set(threshold: u8) -> OracleSet {
+        OracleSet {
+            oracles: Vec::new(),
+            threshold,
+            health_tolerance: 5,
+            trees_tolerance_bps: 500,
+            version: 0,
+            min_stake: 0,
+            unstake_cooldown: 0,
+            challenge_period: 0,
+            dispute_bond: 0,
+            bump: 0,
+        }
+    }
+
+    fn submission(trees_planted: u64, co2_sequestered: u64, ai_health_score: u8) -> ProgressSubmission {
+        let mut report = progress_report(trees_planted, co2_sequestered);
+        report.ai_health_score = ai_health_score;
+        report.satellite_verification_hash = format!("{:0>width$}", trees_planted, width = SATELLITE_HASH_LEN);
+        ProgressSubmission {
+            oracle: Pubkey::new_unique(),
+            report,
+        }
+    }
+
+    #[test]
+    fn aggregate_progress_takes_medians_and_drops_outliers() {
+        let submissions = vec![
+            submission(1_000, 500, 80),
+            submission(1_020, 510, 82),
+            submission(5_000, 500, 80),
+        ];
+
+        let (report, agreeing) = aggregate_progress(&submissions, &test_oracle_set(2)).unwrap();
+        assert_eq!(report.trees_planted, 1_020);
+        assert_eq!(report.co2_sequestered, 500);
+        assert_eq!(report.ai_health_score, 80);
+        assert_eq!(report.satellite_verification_hash, submissions[1].report.satellite_verification_hash);
+        assert_eq!(agreeing, vec![submissions[0].oracle, submissions[1].oracle]);
+    }
+
+    #[test]
+    fn aggregate_progress_fails_without_a_quorum_in_tolerance() {
+        let submissions = vec![
+            submission(1_000, 500, 80),
+            submission(2_000, 500, 80),
+            submission(3_000, 500, 80),
+        ];
+
+        assert!(aggregate_progress(&submissions, &test_oracle_set(2)).is_none());
+    }
+
+    #[test]
+    fn vintage_year_follows_utc_calendar_years() {
+        assert_eq!(vintage_year(0), 1970);
+        assert_eq!(vintage_year(951_782_400), 2000); // 2000-02-29
+        assert_eq!(vintage_year(1_704_067_199), 2023); // 2023-12-31 23:59:59
+        assert_eq!(vintage_year(1_704_067_200), 2024); // 2024-01-01 00:00:00
+    }
+
+    #[test]
+    fn tier_for_amount_picks_the_highest_qualifying_tier() {
+        assert!(tier_for_amount(999_999).is_none());
+        assert!(matches!(tier_for_amount(1_000_000), Some((InvestmentTier::Bronze, 100))));
+        assert!(matches!(tier_for_amount(9_999_999), Some((InvestmentTier::Silver, 110))));
+        assert!(matches!(tier_for_amount(10_000_000), Some((InvestmentTier::Gold, 125))));
+        assert!(matches!(tier_for_amount(50_000_000), Some((InvestmentTier::Platinum, 150))));
+        assert!(matches!(tier_for_amount(u64::MAX), Some((InvestmentTier::Diamond, 200))));
+    }
+}